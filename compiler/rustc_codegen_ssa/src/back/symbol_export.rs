@@ -452,7 +452,7 @@ fn upstream_drop_glue_for_provider<'tcx>(
 }
 
 fn is_unreachable_local_definition_provider(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
-    !tcx.reachable_set(()).contains(&def_id)
+    !tcx.reachable_set(()).is_reachable(def_id)
 }
 
 pub fn provide(providers: &mut Providers) {
@@ -916,6 +916,8 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
         }
     }
 
+    sess.time("dump_reachable_set", || rustc_passes::reachable::dump_reachable_set(tcx));
+
     Ok(())
 }
 
@@ -676,6 +676,11 @@ macro_rules! untracked {
     untracked!(dump_mir_graphviz, true);
     untracked!(dump_mono_stats, SwitchWithOptPath::Enabled(Some("mono-items-dir/".into())));
     untracked!(dump_mono_stats_format, DumpMonoStatsFormat::Json);
+    untracked!(dump_reachable_set, true);
+    untracked!(dump_reachable_set_dot, SwitchWithOptPath::Enabled(Some("reachable-dir/".into())));
+    untracked!(dump_reachable_set_from, Some(String::from("foo::bar")));
+    untracked!(dump_reachable_set_modules, true);
+    untracked!(dump_reachable_set_json, SwitchWithOptPath::Enabled(Some("reachable-dir/".into())));
     untracked!(dylib_lto, true);
     untracked!(emit_stack_sizes, true);
     untracked!(future_incompat_test, true);
@@ -705,6 +710,10 @@ macro_rules! untracked {
     untracked!(proc_macro_execution_strategy, ProcMacroExecutionStrategy::CrossThread);
     untracked!(profile_closures, true);
     untracked!(query_dep_graph, true);
+    untracked!(reachable_all_bodies, true);
+    untracked!(reachable_max_depth, Some(1));
+    untracked!(reachable_set_warn_percent, Some(90));
+    untracked!(reachable_skip_trait_impls, true);
     untracked!(self_profile, SwitchWithOptPath::Enabled(None));
     untracked!(self_profile_events, Some(vec![String::new()]));
     untracked!(shell_argfiles, true);
@@ -31,6 +31,7 @@ pub fn to_sorted_vec(&self) -> Vec<(Symbol, FeatureStability)> {
 }
 pub mod limits;
 pub mod privacy;
+pub mod reachable;
 pub mod region;
 pub mod resolve_bound_vars;
 pub mod stability;
@@ -0,0 +1,408 @@
+//! The result of the reachability pass, which determines the items that other crates may
+//! refer to and that therefore need their metadata (and possibly their MIR) serialized.
+
+use crate::ty::print::with_no_trimmed_paths;
+use crate::ty::TyCtxt;
+use rustc_data_structures::hashes::Hash64;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_data_structures::unord::{UnordItems, UnordMap};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{
+    DefId, DefIdSet, DefPathHash, LocalDefId, LocalDefIdMap, LocalDefIdSet, LocalModDefId,
+    CRATE_DEF_ID,
+};
+use rustc_macros::HashStable;
+use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+#[cfg(test)]
+mod tests;
+
+/// Why an item ended up in the reachable set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReachableReason {
+    /// The item can be named or otherwise obtained through the crate's public interface.
+    Exported,
+    /// The item is a local lang item, which other crates expect to be able to use.
+    LangItem,
+    /// The item has custom linkage, e.g. `#[no_mangle]`, `#[export_name]` or `#[used]`.
+    CustomLinkage,
+    /// The item belongs to a trait impl that is not exported itself, but that other crates
+    /// may still need when monomorphizing.
+    TraitImplItem,
+    /// The item is referenced from the body or initializer of the given reachable item.
+    ReferencedFrom(LocalDefId),
+}
+
+impl ReachableReason {
+    /// Whether `self` is a more significant reason for an item to be reachable than `other`.
+    /// Reasons rank in the order of the variants above. References from two different items
+    /// rank the same, as which of them to report is only decided once all of them are known,
+    /// see `ReachableGraph::choose_referrers`.
+    pub fn takes_precedence_over(self, other: ReachableReason) -> bool {
+        fn rank(reason: ReachableReason) -> u8 {
+            match reason {
+                ReachableReason::Exported => 0,
+                ReachableReason::LangItem => 1,
+                ReachableReason::CustomLinkage => 2,
+                ReachableReason::TraitImplItem => 3,
+                ReachableReason::ReferencedFrom(_) => 4,
+            }
+        }
+        rank(self) < rank(other)
+    }
+}
+
+/// The set of items which are externally reachable, as computed by the `reachable_set` query.
+#[derive(Clone, Debug, Default, HashStable, Encodable, Decodable)]
+pub struct ReachableSet {
+    items: LocalDefIdSet,
+}
+
+impl ReachableSet {
+    pub fn new(items: LocalDefIdSet) -> Self {
+        ReachableSet { items }
+    }
+
+    pub fn is_reachable(&self, def_id: LocalDefId) -> bool {
+        self.items.contains(&def_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn items(&self) -> UnordItems<&LocalDefId, impl Iterator<Item = &LocalDefId>> {
+        self.items.items()
+    }
+
+    /// Returns the path of `def_id`, if it is reachable. Unlike the `LocalDefId` itself, the path
+    /// does not depend on the order in which items were lowered, so it can be used to key
+    /// information about the reachable set that outlives the compilation session.
+    pub fn path_of(&self, tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<String> {
+        self.is_reachable(def_id).then(|| with_no_trimmed_paths!(tcx.def_path_str(def_id)))
+    }
+
+    /// Whether `self` and `other` have the same reachable items.
+    pub fn has_same_items(&self, other: &ReachableSet) -> bool {
+        self.len() == other.len() && self.items().all(|&def_id| other.is_reachable(def_id))
+    }
+
+    /// Hashes the reachable items, so that two sets with the same items hash equal whatever order
+    /// they were found in. The hash is computed from the `DefPathHash`es of the items, so it does
+    /// not change when unrelated items are added to the crate or moved around in it.
+    pub fn content_hash(&self, tcx: TyCtxt<'_>) -> Hash64 {
+        self.content_hash_by(|def_id| tcx.def_path_hash(def_id.to_def_id()))
+    }
+
+    fn content_hash_by(&self, def_path_hash: impl Fn(LocalDefId) -> DefPathHash) -> Hash64 {
+        let hashes = self.items().map(|&def_id| def_path_hash(def_id)).into_sorted_stable_ord();
+        let mut hasher = StableHasher::new();
+        hashes.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the reachable items in an order that is stable across compilation sessions.
+    pub fn to_sorted_vec(&self, tcx: TyCtxt<'_>) -> Vec<LocalDefId> {
+        tcx.with_stable_hashing_context(|hcx| self.items.items().copied().into_sorted(&hcx))
+    }
+
+    /// Returns the items returned by `counted_items` that are not reachable, including trait
+    /// items, impl items and foreign items, sorted by the position of their definitions.
+    pub fn unreachable_items(&self, tcx: TyCtxt<'_>) -> Vec<LocalDefId> {
+        let mut items: Vec<_> =
+            counted_items(tcx).filter(|&def_id| !self.is_reachable(def_id)).collect();
+        items.sort_by_cached_key(|&def_id| tcx.def_span(def_id));
+        items
+    }
+
+    /// Returns the reachable items among those returned by `counted_items`, grouped by the module
+    /// that contains them. The items of each module are sorted by the position of their
+    /// definitions.
+    pub fn items_by_module(&self, tcx: TyCtxt<'_>) -> UnordMap<LocalModDefId, Vec<LocalDefId>> {
+        let mut items: Vec<_> =
+            counted_items(tcx).filter(|&def_id| self.is_reachable(def_id)).collect();
+        items.sort_by_cached_key(|&def_id| tcx.def_span(def_id));
+        let mut by_module: UnordMap<LocalModDefId, Vec<LocalDefId>> = UnordMap::default();
+        for def_id in items {
+            by_module.entry(tcx.parent_module_from_def_id(def_id)).or_default().push(def_id);
+        }
+        by_module
+    }
+
+    /// Returns the number of reachable items and of all the items of each module that contains
+    /// items, to show which modules export the most. Only the items returned by `counted_items`
+    /// are counted, and modules count as items of their parents.
+    pub fn module_report(&self, tcx: TyCtxt<'_>) -> UnordMap<LocalModDefId, (usize, usize)> {
+        let mut report: UnordMap<LocalModDefId, (usize, usize)> = self
+            .items_by_module(tcx)
+            .into_items()
+            .map(|(module, items)| (module, (items.len(), items.len())))
+            .collect();
+        for def_id in self.unreachable_items(tcx) {
+            report.entry(tcx.parent_module_from_def_id(def_id)).or_default().1 += 1;
+        }
+        report
+    }
+}
+
+/// What the reachability pass finds while scanning the bodies, initializers and types of reachable
+/// items: why each item is reachable, the references between items that it follows, the size of
+/// the bodies it scans and which of them other crates may need. Collecting these is only worth it to explain the reachable
+/// set, so they are not part of the result of the `reachable_set` query, and are only computed
+/// for the `-Z dump-reachable-set` family of options.
+#[derive(Clone, Debug, Default)]
+pub struct ReachableGraph {
+    /// The most significant reason for each reachable item to be reachable.
+    reasons: LocalDefIdMap<ReachableReason>,
+    /// The items that were scanned for a reason other than a reference from another item, even
+    /// if they did not end up in the reachable set themselves.
+    roots: LocalDefIdSet,
+    /// The local items referenced from the body, initializer or type of each scanned item.
+    references: LocalDefIdMap<LocalDefIdSet>,
+    /// The local items referenced from any scanned item, so that `is_referenced` does not have to
+    /// go through all of `references`.
+    referenced: LocalDefIdSet,
+    /// The items of other crates referenced from each scanned item.
+    external_references: LocalDefIdMap<DefIdSet>,
+    /// The number of expressions in the bodies scanned for each item.
+    expr_counts: LocalDefIdMap<usize>,
+    /// The items whose bodies other crates may inline or evaluate, and which therefore have to
+    /// be encoded in the metadata.
+    inlinable_bodies: LocalDefIdSet,
+}
+
+impl ReachableGraph {
+    /// Records that `def_id` is reachable for `reason`, unless it was already found to be reachable
+    /// for a more significant one, as ranked by `ReachableReason::takes_precedence_over`.
+    pub fn record_reason(&mut self, def_id: LocalDefId, reason: ReachableReason) {
+        match self.reasons.entry(def_id) {
+            Entry::Occupied(mut entry) => {
+                if reason.takes_precedence_over(*entry.get()) {
+                    entry.insert(reason);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(reason);
+            }
+        }
+    }
+
+    /// Records that `def_id` was scanned for a reason other than a reference from another item.
+    pub fn record_root(&mut self, def_id: LocalDefId) {
+        self.roots.insert(def_id);
+    }
+
+    /// Picks the referrer to report for each item that is only reachable because other items
+    /// reference it, once all the references are recorded: one of the referrers that are the
+    /// fewest references away from the roots, and among those the one with the smallest
+    /// `DefPathHash`. This neither depends on the order in which the references were found nor
+    /// ever picks the item itself or another item of a cycle that it belongs to.
+    pub fn choose_referrers(&mut self, tcx: TyCtxt<'_>) {
+        tcx.with_stable_hashing_context(|hcx| {
+            // Walking the references breadth-first from the roots finds how many references away
+            // from them each item is. The referrers to pick from for an item `hop` references
+            // away are then the items `hop - 1` references away that reference it.
+            let mut hops: LocalDefIdMap<usize> = Default::default();
+            let mut referrers: LocalDefIdMap<LocalDefId> = Default::default();
+            let mut queue: VecDeque<LocalDefId> =
+                self.roots.to_sorted(&hcx, false).into_iter().copied().collect();
+            for &root in &queue {
+                hops.insert(root, 0);
+            }
+            while let Some(from) = queue.pop_front() {
+                let Some(references) = self.references.get(&from) else { continue };
+                let hop = hops[&from] + 1;
+                for &to in references.to_sorted(&hcx, false) {
+                    match hops.entry(to) {
+                        Entry::Vacant(entry) => {
+                            entry.insert(hop);
+                            queue.push_back(to);
+                        }
+                        Entry::Occupied(entry) if *entry.get() != hop => continue,
+                        Entry::Occupied(_) => {}
+                    }
+                    let referrer = referrers.entry(to).or_insert(from);
+                    if tcx.def_path_hash(from.to_def_id()) < tcx.def_path_hash(referrer.to_def_id())
+                    {
+                        *referrer = from;
+                    }
+                }
+            }
+
+            for (def_id, &referrer) in referrers.to_sorted(&hcx, false) {
+                if let Some(reason @ ReachableReason::ReferencedFrom(_)) =
+                    self.reasons.get_mut(def_id)
+                {
+                    *reason = ReachableReason::ReferencedFrom(referrer);
+                }
+            }
+        });
+    }
+
+    /// Returns the most significant reason for `def_id` to be reachable, if it is reachable at all.
+    pub fn reason(&self, def_id: LocalDefId) -> Option<ReachableReason> {
+        self.reasons.get(&def_id).copied()
+    }
+
+    /// Records that the body, initializer or type of the scanned item `from` refers to `to`.
+    pub fn record_reference(&mut self, from: LocalDefId, to: LocalDefId) {
+        self.references.entry(from).or_default().insert(to);
+        self.referenced.insert(to);
+    }
+
+    /// Records that the body, initializer or type of the scanned item `from` refers to `to` from
+    /// another crate.
+    pub fn record_external_reference(&mut self, from: LocalDefId, to: DefId) {
+        self.external_references.entry(from).or_default().insert(to);
+    }
+
+    /// Records that an expression was found in the body or initializer of the scanned item `from`.
+    pub fn record_expr(&mut self, from: LocalDefId) {
+        *self.expr_counts.entry(from).or_default() += 1;
+    }
+
+    /// Forgets the expressions recorded for `def_id`, before its contents are scanned again.
+    pub fn reset_expr_count(&mut self, def_id: LocalDefId) {
+        self.expr_counts.remove(&def_id);
+    }
+
+    /// Records that other crates may need the body of the scanned item `def_id`.
+    pub fn record_inlinable_body(&mut self, def_id: LocalDefId) {
+        self.inlinable_bodies.insert(def_id);
+    }
+
+    /// Returns the local items that the reachability pass found to be referenced from `def_id`.
+    /// Only items whose contents were scanned, i.e. reachable inlinable items, constants and
+    /// statics, have outgoing references.
+    pub fn references_from(&self, def_id: LocalDefId) -> Option<&LocalDefIdSet> {
+        self.references.get(&def_id)
+    }
+
+    /// Whether any of the scanned items references `def_id`.
+    pub fn is_referenced(&self, def_id: LocalDefId) -> bool {
+        self.referenced.contains(&def_id)
+    }
+
+    /// Returns the reachable items that are only reachable because they belong to a trait impl,
+    /// i.e. that are neither exported nor referenced from any of the scanned items. These are the
+    /// items that a more precise treatment of trait impls could leave out of the reachable set.
+    pub fn unreferenced_trait_impl_items(&self) -> LocalDefIdSet {
+        self.reasons
+            .items()
+            .filter(|&(&def_id, &reason)| {
+                reason == ReachableReason::TraitImplItem && !self.is_referenced(def_id)
+            })
+            .map(|(&def_id, _)| def_id)
+            .collect()
+    }
+
+    /// Returns the reachable generic functions that are only reachable because they are exported,
+    /// and that none of the scanned items instantiate. Other crates have to instantiate them
+    /// themselves if they use them at all, so their MIR is only needed by crates that actually
+    /// call them. Methods of traits are left out, as they are instantiated through the impls of
+    /// the trait.
+    pub fn uninstantiated_generics(&self, tcx: TyCtxt<'_>) -> LocalDefIdSet {
+        self.reasons
+            .items()
+            .filter(|&(&def_id, &reason)| {
+                reason == ReachableReason::Exported
+                    && matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+                    && tcx.trait_of_item(def_id.to_def_id()).is_none()
+                    && tcx.generics_of(def_id).requires_monomorphization(tcx)
+                    && !self.is_referenced(def_id)
+            })
+            .map(|(&def_id, _)| def_id)
+            .collect()
+    }
+
+    /// Returns the items of other crates that the reachability pass found to be referenced from
+    /// `def_id`. Like local references, these are only recorded for scanned items.
+    pub fn external_references_from(&self, def_id: LocalDefId) -> Option<&DefIdSet> {
+        self.external_references.get(&def_id)
+    }
+
+    /// Returns `def_id` along with the items it references, the items those reference, and so
+    /// on. As only scanned items have outgoing references, this is the part of the reachable set
+    /// that the reachability pass found by following `def_id` alone.
+    pub fn transitive_references(&self, tcx: TyCtxt<'_>, def_id: LocalDefId) -> LocalDefIdSet {
+        let mut found = LocalDefIdSet::default();
+        let mut stack = vec![def_id];
+        tcx.with_stable_hashing_context(|hcx| {
+            while let Some(def_id) = stack.pop() {
+                if !found.insert(def_id) {
+                    continue;
+                }
+                if let Some(references) = self.references_from(def_id) {
+                    stack.extend(references.to_sorted(&hcx, false).into_iter().copied());
+                }
+            }
+        });
+        found
+    }
+
+    /// Returns the number of expressions in the bodies that the reachability pass scanned for
+    /// `def_id`, if it scanned any. This is a rough estimate of how much of the item other
+    /// crates may inline, and so of how much it adds to the metadata.
+    pub fn expr_count(&self, def_id: LocalDefId) -> Option<usize> {
+        self.expr_counts.get(&def_id).copied()
+    }
+
+    /// Whether other crates may need the body of `def_id`, as opposed to only its signature.
+    /// This holds for reachable inlinable functions, constants, closures and provided trait
+    /// methods, but not for functions whose bodies were only scanned because of
+    /// `-Z reachable-all-bodies`.
+    pub fn has_inlinable_body(&self, def_id: LocalDefId) -> bool {
+        self.inlinable_bodies.contains(&def_id)
+    }
+
+    /// Renders the items of `reachable_set` and the references between them as a Graphviz DOT
+    /// graph, with each item labeled by its path. Items without references from or to them are
+    /// included as separate nodes. The crate root, whose path is empty, is left out.
+    pub fn to_dot(&self, tcx: TyCtxt<'_>, reachable_set: &ReachableSet) -> String {
+        let path = |def_id: LocalDefId| with_no_trimmed_paths!(tcx.def_path_str(def_id));
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        for def_id in reachable_set.to_sorted_vec(tcx) {
+            if def_id == CRATE_DEF_ID {
+                continue;
+            }
+            let from = path(def_id);
+            if let Some(references) = self.references_from(def_id) {
+                edges.extend(
+                    references
+                        .items()
+                        .map(|&to| format!("    {from:?} -> {:?};", path(to)))
+                        .into_sorted_stable_ord(),
+                );
+            }
+            nodes.push(format!("    {from:?};"));
+        }
+        nodes.sort();
+        edges.sort();
+
+        let mut dot = String::from("digraph reachable {\n");
+        for line in nodes.iter().chain(&edges) {
+            dot.push_str(line);
+            dot.push('\n');
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Returns the items that are counted when reporting how much of the crate is reachable: those
+/// that have their own HIR owner, so that fields, variants and closures do not skew the ratio,
+/// except for imports and `extern crate` items, which every crate has. The crate root is not an
+/// item of its own.
+fn counted_items(tcx: TyCtxt<'_>) -> impl Iterator<Item = LocalDefId> + '_ {
+    tcx.hir_crate_items(())
+        .owners()
+        .map(|owner_id| owner_id.def_id)
+        .filter(move |&def_id| !matches!(tcx.def_kind(def_id), DefKind::Use | DefKind::ExternCrate))
+}
@@ -0,0 +1,75 @@
+use super::*;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_hir::def_id::DefIndex;
+
+fn def_id(index: u32) -> LocalDefId {
+    LocalDefId { local_def_index: DefIndex::from_u32(index) }
+}
+
+fn def_path_hash(path: u32) -> DefPathHash {
+    DefPathHash(Fingerprint::new(0u64, u64::from(path)))
+}
+
+fn reachable_set(indices: &[u32]) -> ReachableSet {
+    ReachableSet::new(indices.iter().map(|&index| def_id(index)).collect())
+}
+
+#[test]
+fn test_unreferenced_trait_impl_items() {
+    let mut graph = ReachableGraph::default();
+    graph.record_reason(def_id(1), ReachableReason::Exported);
+    graph.record_reason(def_id(2), ReachableReason::TraitImplItem);
+    graph.record_reason(def_id(3), ReachableReason::TraitImplItem);
+    graph.record_reference(def_id(1), def_id(2));
+    graph.record_reason(def_id(2), ReachableReason::ReferencedFrom(def_id(1)));
+
+    assert!(graph.is_referenced(def_id(2)));
+    assert!(!graph.is_referenced(def_id(3)));
+    assert_eq!(graph.reason(def_id(2)), Some(ReachableReason::TraitImplItem));
+    assert_eq!(graph.unreferenced_trait_impl_items(), [def_id(3)].into_iter().collect());
+}
+
+#[test]
+fn test_content_hash() {
+    // Index `n` stands for the item with path `n`.
+    let path = |def_id: LocalDefId| def_path_hash(def_id.local_def_index.as_u32());
+    let set = reachable_set(&[1, 2, 3]);
+    let same = reachable_set(&[3, 2, 1]);
+
+    assert!(set.has_same_items(&same));
+    assert_eq!(set.content_hash_by(path), same.content_hash_by(path));
+
+    // The same items with other indices, as when an unrelated item is added before them.
+    let renumbered_path = |def_id: LocalDefId| def_path_hash(def_id.local_def_index.as_u32() - 10);
+    let renumbered = reachable_set(&[11, 12, 13]);
+    assert_eq!(set.content_hash_by(path), renumbered.content_hash_by(renumbered_path));
+
+    let other = reachable_set(&[1, 2, 4]);
+    assert!(!set.has_same_items(&other));
+    assert_ne!(set.content_hash_by(path), other.content_hash_by(path));
+}
+
+#[test]
+fn test_inlinable_bodies() {
+    let mut graph = ReachableGraph::default();
+    graph.record_inlinable_body(def_id(1));
+    graph.record_inlinable_body(def_id(3));
+    graph.record_inlinable_body(def_id(1));
+
+    assert!(graph.has_inlinable_body(def_id(1)));
+    assert!(!graph.has_inlinable_body(def_id(2)));
+    assert!(graph.has_inlinable_body(def_id(3)));
+}
+
+#[test]
+fn test_takes_precedence_over() {
+    let from = |index| ReachableReason::ReferencedFrom(def_id(index));
+
+    assert!(ReachableReason::Exported.takes_precedence_over(ReachableReason::LangItem));
+    assert!(ReachableReason::TraitImplItem.takes_precedence_over(from(1)));
+    assert!(!from(1).takes_precedence_over(ReachableReason::TraitImplItem));
+
+    // Which of two referrers to report is left to `ReachableGraph::choose_referrers`.
+    assert!(!from(1).takes_precedence_over(from(2)));
+    assert!(!from(2).takes_precedence_over(from(1)));
+}
@@ -15,6 +15,7 @@
 use crate::middle::exported_symbols::{ExportedSymbol, SymbolExportInfo};
 use crate::middle::lib_features::LibFeatures;
 use crate::middle::privacy::EffectiveVisibilities;
+use crate::middle::reachable::ReachableSet;
 use crate::middle::resolve_bound_vars::{ObjectLifetimeDefault, ResolveBoundVars, ResolvedArg};
 use crate::middle::stability::{self, DeprecationEntry};
 use crate::mir;
@@ -1121,7 +1122,7 @@
         desc { "checking for private elements in public interfaces" }
     }
 
-    query reachable_set(_: ()) -> &'tcx LocalDefIdSet {
+    query reachable_set(_: ()) -> &'tcx ReachableSet {
         arena_cache
         desc { "reachability" }
         cache_on_disk_if { true }
@@ -109,6 +109,9 @@ passes_continue_labeled_block =
     .label = labeled blocks cannot be `continue`'d
     .block_label = labeled block the `continue` points to
 
+passes_couldnt_dump_reachable_set =
+    unexpected error occurred while dumping the reachable set: {$error}
+
 passes_coverage_fn_defn =
     `#[coverage]` may only be applied to function definitions
 
@@ -465,6 +468,10 @@ passes_missing_panic_handler =
 passes_missing_stability_attr =
     {$descr} has missing stability attribute
 
+passes_mostly_reachable =
+    {$reachable} of the {$total} items of this crate are reachable from other crates, more than the {$percent}% allowed by `-Z reachable-set-warn-percent`
+    .note = this often means that items are public that were not meant to be
+
 passes_multiple_rustc_main =
     multiple functions with a `#[rustc_main]` attribute
     .first = first `#[rustc_main]` function
@@ -588,6 +595,17 @@ passes_pass_by_value =
 
 passes_proc_macro_bad_sig = {$kind} has incorrect signature
 
+passes_reachable_item =
+    `{$item}` is reachable: {$reason}
+    .note = references {$referenced}
+    .upstream_note = references upstream {$externally_referenced}
+    .unreferenced_note = no inlinable body or initializer of a reachable item refers to it, it is only reachable as part of a trait impl
+    .uninstantiated_note = it is generic and no inlinable body or initializer of a reachable item instantiates it
+
+passes_reachable_module =
+    {$reachable} of the {$total} items of {$module} are reachable from other crates
+    .note = not reachable: {$unreachable_items}
+
 passes_repr_conflicting =
     conflicting representation hints
 
@@ -699,6 +717,9 @@ passes_unknown_lang_item =
     definition of an unknown language item: `{$name}`
     .label = definition of unknown language item `{$name}`
 
+passes_unknown_reachable_root =
+    `-Z dump-reachable-set-from` names no reachable item: `{$path}`
+
 passes_unlabeled_cf_in_while_condition =
     `break` or `continue` with no label in the condition of a `while` loop
     .label = unlabeled `{$cf_type}` in the condition of a `while` loop
@@ -544,6 +544,51 @@ pub struct NoMangle {
     pub span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_couldnt_dump_reachable_set)]
+pub struct CouldntDumpReachableSet {
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(passes_reachable_item)]
+pub struct ReachableItem {
+    #[primary_span]
+    pub span: Span,
+    pub item: String,
+    pub reason: String,
+    #[note]
+    pub references: Option<()>,
+    pub referenced: String,
+    #[note(passes_upstream_note)]
+    pub external_references: Option<()>,
+    pub externally_referenced: String,
+    #[note(passes_unreferenced_note)]
+    pub unreferenced: Option<()>,
+    #[note(passes_uninstantiated_note)]
+    pub uninstantiated: Option<()>,
+}
+
+#[derive(Diagnostic)]
+#[diag(passes_reachable_module)]
+pub struct ReachableModule {
+    pub module: String,
+    pub reachable: usize,
+    pub total: usize,
+    #[note]
+    pub unreachable: Option<()>,
+    pub unreachable_items: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(passes_mostly_reachable)]
+#[note]
+pub struct MostlyReachable {
+    pub reachable: usize,
+    pub total: usize,
+    pub percent: usize,
+}
+
 #[derive(Diagnostic)]
 #[diag(passes_repr_ident, code = E0565)]
 pub struct ReprIdent {
@@ -849,6 +894,12 @@ pub struct UnknownLangItem {
     pub name: Symbol,
 }
 
+#[derive(Diagnostic)]
+#[diag(passes_unknown_reachable_root)]
+pub struct UnknownReachableRoot {
+    pub path: String,
+}
+
 pub struct InvalidAttrAtCrateLevel {
     pub span: Span,
     pub sugg_span: Option<Span>,
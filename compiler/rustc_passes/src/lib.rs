@@ -37,7 +37,7 @@
 mod liveness;
 pub mod loops;
 mod naked_functions;
-mod reachable;
+pub mod reachable;
 pub mod stability;
 mod upvars;
 mod weak_lang_items;
@@ -5,21 +5,32 @@
 // makes all other generics or inline functions that it references
 // reachable as well.
 
-use hir::def_id::LocalDefIdSet;
+use crate::errors::{
+    CouldntDumpReachableSet, MostlyReachable, ReachableItem, ReachableModule, UnknownReachableRoot,
+};
+use hir::def_id::{LocalDefIdMap, LocalDefIdSet};
 use rustc_data_structures::stack::ensure_sufficient_stack;
+use rustc_data_structures::unord::UnordMap;
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
-use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::def_id::{DefId, LocalDefId, LocalModDefId, CRATE_DEF_ID, LOCAL_CRATE};
 use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::Node;
 use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
 use rustc_middle::middle::privacy::{self, Level};
+use rustc_middle::middle::reachable::{ReachableGraph, ReachableReason, ReachableSet};
 use rustc_middle::mir::interpret::{ConstAllocation, GlobalAlloc};
 use rustc_middle::query::Providers;
+use rustc_middle::ty::adjustment::Adjust;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, ExistentialTraitRef, TyCtxt};
 use rustc_privacy::DefIdVisitor;
-use rustc_session::config::CrateType;
+use rustc_session::config::{CrateType, SwitchWithOptPath};
+use rustc_span::Symbol;
 use rustc_target::spec::abi::Abi;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 
 fn item_might_be_inlined(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
     tcx.generics_of(def_id).requires_monomorphization(tcx)
@@ -37,9 +48,24 @@ struct ReachableContext<'tcx> {
     // A worklist of item IDs. Each item ID in this worklist will be inlined
     // and will be scanned for further references.
     // FIXME(eddyb) benchmark if this would be faster as a `VecDeque`.
-    worklist: Vec<LocalDefId>,
+    worklist: Vec<(LocalDefId, ReachableReason)>,
+    // Items that `propagate_item` has already handled. Popular items are
+    // referenced from many bodies, and need to be looked at only once.
+    propagated: LocalDefIdSet,
+    // The item whose node is currently being scanned by `propagate_node`.
+    scanned_item: Option<LocalDefId>,
+    // Why the items are reachable and what scanning them finds, collected only to explain the
+    // reachable set, see `reachable_graph`.
+    graph: Option<ReachableGraph>,
     // Whether any output of this compilation is a library
     any_library: bool,
+    // Whether to scan the bodies of all reachable functions, not only those
+    // of inlinable ones (`-Z reachable-all-bodies`).
+    all_bodies: bool,
+    // How many references away from the roots items are still scanned
+    // (`-Z reachable-max-depth`), and the depth of each scanned item.
+    max_depth: Option<usize>,
+    depths: LocalDefIdMap<usize>,
 }
 
 impl<'tcx> Visitor<'tcx> for ReachableContext<'tcx> {
@@ -52,16 +78,53 @@ fn visit_nested_body(&mut self, body: hir::BodyId) {
     }
 
     fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let (Some(graph), Some(scanned_item)) = (&mut self.graph, self.scanned_item) {
+            graph.record_expr(scanned_item);
+        }
+
         let res = match expr.kind {
             hir::ExprKind::Path(ref qpath) => {
                 Some(self.typeck_results().qpath_res(qpath, expr.hir_id))
             }
+            // Struct expressions, including functional record updates, name the struct
+            // or variant they build through a path of their own.
+            hir::ExprKind::Struct(qpath, ..) => {
+                Some(self.typeck_results().qpath_res(qpath, expr.hir_id))
+            }
             hir::ExprKind::MethodCall(..) => self
                 .typeck_results()
                 .type_dependent_def(expr.hir_id)
                 .map(|(kind, def_id)| Res::Def(kind, def_id)),
+            // Calls of values that are not functions and overloaded operators resolve to the
+            // method of their `Fn*` or operator trait, but end up calling the method of the
+            // impl for the callee or operand types, which is local if these types are.
+            hir::ExprKind::Call(..)
+            | hir::ExprKind::Binary(..)
+            | hir::ExprKind::AssignOp(..)
+            | hir::ExprKind::Unary(..)
+            | hir::ExprKind::Index(..) => {
+                self.typeck_results().type_dependent_def(expr.hir_id).map(|(kind, def_id)| {
+                    self.propagate_impl_method(
+                        def_id,
+                        self.typeck_results().node_args(expr.hir_id),
+                    );
+                    Res::Def(kind, def_id)
+                })
+            }
+            // The target type of a cast can name a local type alias, which type
+            // checking replaces with the type it stands for, so both it and the type
+            // it stands for are visited.
+            hir::ExprKind::Cast(_, ty) => {
+                self.visit(self.typeck_results().expr_ty(expr));
+                if let hir::TyKind::Path(hir::QPath::Resolved(_, path)) = ty.kind {
+                    Some(path.res)
+                } else {
+                    None
+                }
+            }
             hir::ExprKind::Closure(&hir::Closure { def_id, .. }) => {
-                self.reachable_symbols.insert(def_id);
+                self.record_reference(def_id);
+                self.mark_reachable(def_id, self.referenced_from());
                 None
             }
             _ => None,
@@ -69,16 +132,55 @@ fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
 
         if let Some(res) = res {
             self.propagate_item(res);
+            // The generic arguments of a path or a method call can name local types
+            // that are not mentioned anywhere else, such as marker types.
+            if let Some(args) = self.typeck_results().node_args_opt(expr.hir_id) {
+                self.visit(args);
+            }
+        }
+
+        // Autoderef and deref coercions call `Deref::deref` or `DerefMut::deref_mut`
+        // for the types they dereference, without any expression to resolve.
+        let adjustments = self.typeck_results().expr_adjustments(expr);
+        if adjustments.iter().any(|adjustment| matches!(adjustment.kind, Adjust::Deref(Some(_)))) {
+            let mut source = self.typeck_results().expr_ty(expr);
+            for adjustment in adjustments {
+                if let Adjust::Deref(Some(overloaded)) = adjustment.kind {
+                    let method = overloaded.method_call(self.tcx, source);
+                    self.visit(method);
+                    // Like for overloaded operators, the impl method is what gets called.
+                    if let ty::FnDef(def_id, args) = *method.kind() {
+                        self.propagate_impl_method(def_id, args);
+                    }
+                }
+                source = adjustment.target;
+            }
         }
 
         intravisit::walk_expr(self, expr)
     }
 
+    fn visit_pat(&mut self, pat: &'tcx hir::Pat<'tcx>) {
+        // Patterns name the variants, structs and constants they match against through
+        // paths of their own, which are not expressions.
+        match pat.kind {
+            hir::PatKind::Path(ref qpath)
+            | hir::PatKind::TupleStruct(ref qpath, ..)
+            | hir::PatKind::Struct(ref qpath, ..) => {
+                self.propagate_item(self.typeck_results().qpath_res(qpath, pat.hir_id));
+            }
+            _ => {}
+        }
+
+        intravisit::walk_pat(self, pat)
+    }
+
     fn visit_inline_asm(&mut self, asm: &'tcx hir::InlineAsm<'tcx>, id: hir::HirId) {
         for (op, _) in asm.operands {
             if let hir::InlineAsmOperand::SymStatic { def_id, .. } = op {
                 if let Some(def_id) = def_id.as_local() {
-                    self.reachable_symbols.insert(def_id);
+                    self.record_reference(def_id);
+                    self.mark_reachable(def_id, self.referenced_from());
                 }
             }
         }
@@ -96,6 +198,52 @@ fn typeck_results(&self) -> &'tcx ty::TypeckResults<'tcx> {
             .expect("`ReachableContext::typeck_results` called outside of body")
     }
 
+    /// The reason to record for items referenced by the item currently being scanned.
+    #[track_caller]
+    fn referenced_from(&self) -> ReachableReason {
+        ReachableReason::ReferencedFrom(
+            self.scanned_item
+                .expect("`ReachableContext::referenced_from` called outside of `propagate_node`"),
+        )
+    }
+
+    /// Records that the item currently being scanned refers to `def_id`.
+    fn record_reference(&mut self, def_id: LocalDefId) {
+        let from = self
+            .scanned_item
+            .expect("`ReachableContext::record_reference` called outside of `propagate_node`");
+        if let Some(graph) = &mut self.graph {
+            graph.record_reference(from, def_id);
+        }
+    }
+
+    /// Records that the item currently being scanned refers to `def_id` from another crate.
+    fn record_external_reference(&mut self, def_id: DefId) {
+        let from = self.scanned_item.expect(
+            "`ReachableContext::record_external_reference` called outside of `propagate_node`",
+        );
+        if let Some(graph) = &mut self.graph {
+            graph.record_external_reference(from, def_id);
+        }
+    }
+
+    /// Records that other crates may need the body of `def_id`, as opposed to bodies that are
+    /// only scanned because of `-Z reachable-all-bodies`.
+    fn record_inlinable_body(&mut self, def_id: LocalDefId) {
+        if let Some(graph) = &mut self.graph {
+            graph.record_inlinable_body(def_id);
+        }
+    }
+
+    /// Adds `def_id` to the reachable set, recording `reason` for it if there is a graph to
+    /// record it into. Returns whether `def_id` was not reachable before.
+    fn mark_reachable(&mut self, def_id: LocalDefId, reason: ReachableReason) -> bool {
+        if let Some(graph) = &mut self.graph {
+            graph.record_reason(def_id, reason);
+        }
+        self.reachable_symbols.insert(def_id)
+    }
+
     // Returns true if the given def ID represents a local item that is
     // eligible for inlining and false otherwise.
     fn def_id_represents_local_inlined_item(&self, def_id: DefId) -> bool {
@@ -128,16 +276,65 @@ fn def_id_represents_local_inlined_item(&self, def_id: DefId) -> bool {
     // Step 2: Mark all symbols that the symbols on the worklist touch.
     fn propagate(&mut self) {
         let mut scanned = LocalDefIdSet::default();
-        while let Some(search_item) = self.worklist.pop() {
-            if !scanned.insert(search_item) {
+        while let Some((search_item, reason)) = self.worklist.pop() {
+            if let Some(graph) = &mut self.graph {
+                if !matches!(reason, ReachableReason::ReferencedFrom(_)) {
+                    graph.record_root(search_item);
+                }
+            }
+            // An item that was already scanned may still be reachable for a more
+            // significant reason than the one it was scanned for.
+            if self.graph.is_some() && self.reachable_symbols.contains(&search_item) {
+                self.mark_reachable(search_item, reason);
+            }
+
+            if let Some(max_depth) = self.max_depth {
+                // Items are pushed from the item being scanned, so the depth of the item
+                // that referenced this one is already known.
+                let depth = match reason {
+                    ReachableReason::ReferencedFrom(from) => self.depths[&from] + 1,
+                    _ => 0,
+                };
+                // An item first reached far from the roots may be reached again closer to
+                // them later on, and must then be scanned again, as it may now lead to items
+                // that were beyond the limit before.
+                if self
+                    .depths
+                    .get(&search_item)
+                    .is_some_and(|&scanned_depth| scanned_depth <= depth)
+                {
+                    continue;
+                }
+                if depth >= max_depth {
+                    // Mark the item at the boundary, but don't look for references in it.
+                    if self.any_library {
+                        self.mark_reachable(search_item, reason);
+                    }
+                    continue;
+                }
+                if self.depths.insert(search_item, depth).is_some() {
+                    // Scanning the item again finds the same references, which are kept in
+                    // sets, but its expressions must not be counted twice.
+                    if let Some(graph) = &mut self.graph {
+                        graph.reset_expr_count(search_item);
+                    }
+                }
+            } else if !scanned.insert(search_item) {
                 continue;
             }
 
-            self.propagate_node(&self.tcx.hir_node_by_def_id(search_item), search_item);
+            self.scanned_item = Some(search_item);
+            self.propagate_node(&self.tcx.hir_node_by_def_id(search_item), search_item, reason);
+            self.scanned_item = None;
         }
     }
 
-    fn propagate_node(&mut self, node: &Node<'tcx>, search_item: LocalDefId) {
+    fn propagate_node(
+        &mut self,
+        node: &Node<'tcx>,
+        search_item: LocalDefId,
+        reason: ReachableReason,
+    ) {
         if !self.any_library {
             // If we are building an executable, only explicitly extern
             // types need to be exported.
@@ -160,21 +357,25 @@ fn propagate_node(&mut self, node: &Node<'tcx>, search_item: LocalDefId) {
             let std_internal =
                 codegen_attrs.flags.contains(CodegenFnAttrFlags::RUSTC_STD_INTERNAL_SYMBOL);
             if reachable || is_extern || std_internal {
-                self.reachable_symbols.insert(search_item);
+                self.mark_reachable(search_item, reason);
             }
         } else {
             // If we are building a library, then reachable symbols will
             // continue to participate in linkage after this product is
             // produced. In this case, we traverse the ast node, recursing on
             // all reachable nodes from this one.
-            self.reachable_symbols.insert(search_item);
+            self.mark_reachable(search_item, reason);
         }
 
         match *node {
             Node::Item(item) => {
                 match item.kind {
                     hir::ItemKind::Fn(.., body) => {
-                        if item_might_be_inlined(self.tcx, item.owner_id.into()) {
+                        let inlinable = item_might_be_inlined(self.tcx, item.owner_id.into());
+                        if inlinable {
+                            self.record_inlinable_body(search_item);
+                        }
+                        if self.all_bodies || inlinable {
                             self.visit_nested_body(body);
                         }
                     }
@@ -183,6 +384,7 @@ fn propagate_node(&mut self, node: &Node<'tcx>, search_item: LocalDefId) {
                     // unconditionally, so we need to make sure that their
                     // contents are also reachable.
                     hir::ItemKind::Const(_, _, init) => {
+                        self.record_inlinable_body(search_item);
                         self.visit_nested_body(init);
                     }
                     hir::ItemKind::Static(..) => {
@@ -191,6 +393,16 @@ fn propagate_node(&mut self, node: &Node<'tcx>, search_item: LocalDefId) {
                         }
                     }
 
+                    // Explicit discriminants are evaluated by other crates just
+                    // like constants, so whatever they refer to must be reachable.
+                    hir::ItemKind::Enum(ref enum_def, _) => {
+                        for variant in enum_def.variants {
+                            if let Some(ref disr_expr) = variant.disr_expr {
+                                self.visit_nested_body(disr_expr.body);
+                            }
+                        }
+                    }
+
                     // These are normal, nothing reachable about these
                     // inherently and their children are already in the
                     // worklist, as determined by the privacy pass
@@ -205,7 +417,6 @@ fn propagate_node(&mut self, node: &Node<'tcx>, search_item: LocalDefId) {
                     | hir::ItemKind::Trait(..)
                     | hir::ItemKind::TraitAlias(..)
                     | hir::ItemKind::Struct(..)
-                    | hir::ItemKind::Enum(..)
                     | hir::ItemKind::Union(..)
                     | hir::ItemKind::GlobalAsm(..) => {}
                 }
@@ -218,6 +429,7 @@ fn propagate_node(&mut self, node: &Node<'tcx>, search_item: LocalDefId) {
                     }
                     hir::TraitItemKind::Const(_, Some(body_id))
                     | hir::TraitItemKind::Fn(_, hir::TraitFn::Provided(body_id)) => {
+                        self.record_inlinable_body(search_item);
                         self.visit_nested_body(body_id);
                     }
                     hir::TraitItemKind::Type(..) => {}
@@ -225,10 +437,16 @@ fn propagate_node(&mut self, node: &Node<'tcx>, search_item: LocalDefId) {
             }
             Node::ImplItem(impl_item) => match impl_item.kind {
                 hir::ImplItemKind::Const(_, body) => {
+                    self.record_inlinable_body(search_item);
                     self.visit_nested_body(body);
                 }
                 hir::ImplItemKind::Fn(_, body) => {
-                    if item_might_be_inlined(self.tcx, impl_item.hir_id().owner.to_def_id()) {
+                    let inlinable =
+                        item_might_be_inlined(self.tcx, impl_item.hir_id().owner.to_def_id());
+                    if inlinable {
+                        self.record_inlinable_body(search_item);
+                    }
+                    if self.all_bodies || inlinable {
                         self.visit_nested_body(body)
                     }
                 }
@@ -238,11 +456,20 @@ fn propagate_node(&mut self, node: &Node<'tcx>, search_item: LocalDefId) {
                 kind: hir::ExprKind::Closure(&hir::Closure { body, .. }),
                 ..
             }) => {
+                self.record_inlinable_body(search_item);
                 self.visit_nested_body(body);
             }
+            Node::ForeignItem(foreign_item) => match foreign_item.kind {
+                hir::ForeignItemKind::Fn(..) => {
+                    self.visit(self.tcx.fn_sig(search_item).instantiate_identity());
+                }
+                hir::ForeignItemKind::Static(..) => {
+                    self.visit(self.tcx.type_of(search_item).instantiate_identity());
+                }
+                hir::ForeignItemKind::Type => {}
+            },
             // Nothing to recurse on for these
-            Node::ForeignItem(_)
-            | Node::Variant(_)
+            Node::Variant(_)
             | Node::Ctor(..)
             | Node::Field(_)
             | Node::Ty(_)
@@ -294,12 +521,21 @@ fn propagate_from_alloc(&mut self, alloc: ConstAllocation<'tcx>) {
 
     fn propagate_item(&mut self, res: Res) {
         let Res::Def(kind, def_id) = res else { return };
-        let Some(def_id) = def_id.as_local() else { return };
+        let Some(def_id) = def_id.as_local() else {
+            self.record_external_reference(def_id);
+            return;
+        };
+        self.record_reference(def_id);
+        // With a depth limit, items have to be propagated again when they are reached closer
+        // to the roots, see `propagate`.
+        if self.max_depth.is_none() && !self.propagated.insert(def_id) {
+            return;
+        }
         match kind {
             DefKind::Static { nested: true, .. } => {
                 // This is the main purpose of this function: add the def_id we find
                 // to `reachable_symbols`.
-                if self.reachable_symbols.insert(def_id) {
+                if self.mark_reachable(def_id, self.referenced_from()) {
                     if let Ok(alloc) = self.tcx.eval_static_initializer(def_id) {
                         // This cannot cause infinite recursion, because we abort by inserting into the
                         // work list once we hit a normal static. Nested statics, even if they somehow
@@ -313,13 +549,54 @@ fn propagate_item(&mut self, res: Res) {
             // Reachable constants and reachable statics can have their contents inlined
             // into other crates. Mark them as reachable and recurse into their body.
             DefKind::Const | DefKind::AssocConst | DefKind::Static { .. } => {
-                self.worklist.push(def_id);
+                self.worklist.push((def_id, self.referenced_from()));
+            }
+            // The signatures of foreign items can name local types, which have to
+            // be reachable as well if the foreign item is. Executables only need the foreign
+            // item itself, which `propagate_node` would not mark for them.
+            _ if self.tcx.is_foreign_item(def_id) => {
+                self.mark_reachable(def_id, self.referenced_from());
+                if self.any_library {
+                    self.worklist.push((def_id, self.referenced_from()));
+                }
+            }
+            DefKind::Fn | DefKind::AssocFn if self.all_bodies => {
+                self.worklist.push((def_id, self.referenced_from()));
+            }
+            // Closures are scanned as part of the body that defines them, except for those
+            // in the initializer of a static, which is evaluated rather than scanned. They
+            // are found through the function pointers that it evaluates to instead.
+            DefKind::Closure
+                if self.any_library
+                    && matches!(
+                        self.tcx.def_kind(self.tcx.typeck_root_def_id(def_id.to_def_id())),
+                        DefKind::Static { .. }
+                    ) =>
+            {
+                self.worklist.push((def_id, self.referenced_from()));
             }
             _ => {
                 if self.def_id_represents_local_inlined_item(def_id.to_def_id()) {
-                    self.worklist.push(def_id);
+                    self.worklist.push((def_id, self.referenced_from()));
                 } else {
-                    self.reachable_symbols.insert(def_id);
+                    self.mark_reachable(def_id, self.referenced_from());
+                }
+            }
+        }
+    }
+
+    /// Propagates the method of the impl that a call of the trait method `def_id` with the
+    /// generic arguments `args` resolves to, if it can be resolved from the body being scanned.
+    fn propagate_impl_method(&mut self, def_id: DefId, args: ty::GenericArgsRef<'tcx>) {
+        let param_env = self.tcx.param_env(self.typeck_results().hir_owner);
+        let Ok(args) = self.tcx.try_normalize_erasing_regions(param_env, args) else { return };
+        if let Ok(Some(instance)) = ty::Instance::resolve(self.tcx, param_env, def_id, args) {
+            if let ty::InstanceDef::Item(impl_item_def_id) = instance.def {
+                if impl_item_def_id != def_id {
+                    self.propagate_item(Res::Def(
+                        self.tcx.def_kind(impl_item_def_id),
+                        impl_item_def_id,
+                    ));
                 }
             }
         }
@@ -346,14 +623,16 @@ fn visit_def_id(
 fn check_item<'tcx>(
     tcx: TyCtxt<'tcx>,
     id: hir::ItemId,
-    worklist: &mut Vec<LocalDefId>,
+    worklist: &mut Vec<(LocalDefId, ReachableReason)>,
     effective_visibilities: &privacy::EffectiveVisibilities,
+    traits_with_pushed_methods: &mut LocalDefIdSet,
+    skip_trait_impls: bool,
 ) {
     if has_custom_linkage(tcx, id.owner_id.def_id) {
-        worklist.push(id.owner_id.def_id);
+        worklist.push((id.owner_id.def_id, ReachableReason::CustomLinkage));
     }
 
-    if !matches!(tcx.def_kind(id.owner_id), DefKind::Impl { of_trait: true }) {
+    if !matches!(tcx.def_kind(id.owner_id), DefKind::Impl { of_trait: true }) || skip_trait_impls {
         return;
     }
 
@@ -363,18 +642,26 @@ fn check_item<'tcx>(
     }
 
     let items = tcx.associated_item_def_ids(id.owner_id);
-    worklist.extend(items.iter().map(|ii_ref| ii_ref.expect_local()));
+    worklist
+        .extend(items.iter().map(|ii_ref| (ii_ref.expect_local(), ReachableReason::TraitImplItem)));
 
     let Some(trait_def_id) = tcx.trait_id_of_impl(id.owner_id.to_def_id()) else {
         unreachable!();
     };
 
-    if !trait_def_id.is_local() {
+    let Some(trait_def_id) = trait_def_id.as_local() else {
+        return;
+    };
+
+    // A trait can have many impls, but its provided methods only need to be pushed once.
+    if !traits_with_pushed_methods.insert(trait_def_id) {
         return;
     }
 
-    worklist
-        .extend(tcx.provided_trait_methods(trait_def_id).map(|assoc| assoc.def_id.expect_local()));
+    worklist.extend(
+        tcx.provided_trait_methods(trait_def_id.to_def_id())
+            .map(|assoc| (assoc.def_id.expect_local(), ReachableReason::TraitImplItem)),
+    );
 }
 
 fn has_custom_linkage(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
@@ -394,19 +681,55 @@ fn has_custom_linkage(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
         || codegen_attrs.flags.contains(CodegenFnAttrFlags::USED_LINKER)
 }
 
-fn reachable_set(tcx: TyCtxt<'_>, (): ()) -> LocalDefIdSet {
+/// A cheap estimate of the number of items in the crate, used to size the
+/// reachable set up front instead of rehashing it as it grows.
+fn estimated_item_count(tcx: TyCtxt<'_>) -> usize {
+    tcx.hir_crate_items(()).owners().count()
+}
+
+fn reachable_set(tcx: TyCtxt<'_>, (): ()) -> ReachableSet {
+    compute_reachable_set(tcx, None).0
+}
+
+/// Computes the reachable set again, along with the references between its items, for the
+/// `-Z dump-reachable-set` family of options. This is also where the options that change how
+/// the reachable set is computed to measure its parts, `-Z reachable-all-bodies`,
+/// `-Z reachable-max-depth` and `-Z reachable-skip-trait-impls`, take effect, so that they never
+/// change the set that is exported and encoded in the metadata.
+fn reachable_graph(tcx: TyCtxt<'_>) -> (ReachableSet, ReachableGraph) {
+    let (reachable_set, graph) = compute_reachable_set(tcx, Some(ReachableGraph::default()));
+    (reachable_set, graph.unwrap())
+}
+
+/// Computes the reachable set, recording the references between its items into `graph` if
+/// there is one, in which case the reachable set is only used to explain itself, see
+/// `reachable_graph`.
+fn compute_reachable_set(
+    tcx: TyCtxt<'_>,
+    graph: Option<ReachableGraph>,
+) -> (ReachableSet, Option<ReachableGraph>) {
     let effective_visibilities = &tcx.effective_visibilities(());
+    let analysis = graph.is_some();
 
     let any_library = tcx
         .crate_types()
         .iter()
         .any(|ty| *ty == CrateType::Rlib || *ty == CrateType::Dylib || *ty == CrateType::ProcMacro);
+    // Executables only need the few items with custom linkage or a non-Rust ABI
+    // to be reachable, so only libraries are worth allocating for up front.
+    let capacity = if any_library { estimated_item_count(tcx) } else { 0 };
     let mut reachable_context = ReachableContext {
         tcx,
         maybe_typeck_results: None,
-        reachable_symbols: Default::default(),
+        reachable_symbols: LocalDefIdSet::with_capacity(capacity),
         worklist: Vec::new(),
+        propagated: Default::default(),
+        scanned_item: None,
+        graph,
         any_library,
+        all_bodies: analysis && tcx.sess.opts.unstable_opts.reachable_all_bodies,
+        max_depth: if analysis { tcx.sess.opts.unstable_opts.reachable_max_depth } else { None },
+        depths: Default::default(),
     };
 
     // Step 1: Seed the worklist with all nodes which were found to be public as
@@ -414,19 +737,22 @@ fn reachable_set(tcx: TyCtxt<'_>, (): ()) -> LocalDefIdSet {
     //         If other crates link to us, they're going to expect to be able to
     //         use the lang items, so we need to be sure to mark them as
     //         exported.
-    reachable_context.worklist = effective_visibilities
-        .iter()
-        .filter_map(|(&id, effective_vis)| {
-            effective_vis.is_public_at_level(Level::ReachableThroughImplTrait).then_some(id)
-        })
-        .collect::<Vec<_>>();
+    tcx.sess.time("reachable_set_roots", || {
+        reachable_context.worklist = effective_visibilities
+            .iter()
+            .filter_map(|(&id, effective_vis)| {
+                effective_vis
+                    .is_public_at_level(Level::ReachableThroughImplTrait)
+                    .then_some((id, ReachableReason::Exported))
+            })
+            .collect::<Vec<_>>();
 
-    for (_, def_id) in tcx.lang_items().iter() {
-        if let Some(def_id) = def_id.as_local() {
-            reachable_context.worklist.push(def_id);
+        for (_, def_id) in tcx.lang_items().iter() {
+            if let Some(def_id) = def_id.as_local() {
+                reachable_context.worklist.push((def_id, ReachableReason::LangItem));
+            }
         }
-    }
-    {
+
         // Some methods from non-exported (completely private) trait impls still have to be
         // reachable if they are called from inlinable code. Generally, it's not known until
         // monomorphization if a specific trait impl item can be reachable or not. So, we
@@ -436,25 +762,276 @@ fn reachable_set(tcx: TyCtxt<'_>, (): ()) -> LocalDefIdSet {
         // trait items are used from inlinable code through method call syntax or UFCS, or their
         // trait is a lang item.
         let crate_items = tcx.hir_crate_items(());
+        let mut traits_with_pushed_methods = LocalDefIdSet::default();
 
         for id in crate_items.items() {
-            check_item(tcx, id, &mut reachable_context.worklist, effective_visibilities);
+            check_item(
+                tcx,
+                id,
+                &mut reachable_context.worklist,
+                effective_visibilities,
+                &mut traits_with_pushed_methods,
+                analysis && tcx.sess.opts.unstable_opts.reachable_skip_trait_impls,
+            );
         }
 
         for id in crate_items.impl_items() {
             if has_custom_linkage(tcx, id.owner_id.def_id) {
-                reachable_context.worklist.push(id.owner_id.def_id);
+                reachable_context
+                    .worklist
+                    .push((id.owner_id.def_id, ReachableReason::CustomLinkage));
             }
         }
-    }
+    });
 
     // Step 2: Mark all symbols that the symbols on the worklist touch.
-    reachable_context.propagate();
+    tcx.sess.time("reachable_set_propagate", || reachable_context.propagate());
+
+    // Which reference to an item is found first depends on the order of the worklist, so the
+    // referrer to report is only picked once all of them are known.
+    if let Some(graph) = &mut reachable_context.graph {
+        graph.choose_referrers(tcx);
+    }
 
     debug!("Inline reachability shows: {:?}", reachable_context.reachable_symbols);
 
     // Return the set of reachable symbols.
-    reachable_context.reachable_symbols
+    let reachable_set = ReachableSet::new(reachable_context.reachable_symbols);
+    (reachable_set, reachable_context.graph)
+}
+
+/// Warns if more than `percent` percent of the items of the crate are reachable, which often
+/// means that much more of it was made public than intended (`-Z reachable-set-warn-percent`).
+/// The items are counted like for `-Z dump-reachable-set-modules`.
+fn warn_if_mostly_reachable(tcx: TyCtxt<'_>, reachable_set: &ReachableSet, percent: usize) {
+    let report = reachable_set.module_report(tcx);
+    let reachable: usize = report.items().map(|(_, &(reachable, _))| reachable).sum();
+    let total: usize = report.items().map(|(_, &(_, total))| total).sum();
+    if total > 0 && reachable * 100 > total * percent {
+        tcx.dcx().emit_warn(MostlyReachable { reachable, total, percent });
+    }
+}
+
+/// Emits the output of the `-Z dump-reachable-set` family of options for the local crate, and the
+/// warning of `-Z reachable-set-warn-percent`. The `reachable_set` query is cached by incremental
+/// compilation, so this is called after the analysis passes, which run in every session, rather
+/// than from the query itself.
+pub fn dump_reachable_set(tcx: TyCtxt<'_>) {
+    let opts = &tcx.sess.opts.unstable_opts;
+    if let Some(percent) = opts.reachable_set_warn_percent {
+        warn_if_mostly_reachable(tcx, tcx.reachable_set(()), percent);
+    }
+
+    if !(opts.dump_reachable_set
+        || opts.dump_reachable_set_modules
+        || opts.dump_reachable_set_from.is_some()
+        || opts.dump_reachable_set_json.enabled()
+        || opts.dump_reachable_set_dot.enabled())
+    {
+        return;
+    }
+    let (ref reachable_set, ref graph) = reachable_graph(tcx);
+
+    if opts.dump_reachable_set {
+        dump_reachable_items(tcx, graph, reachable_set.to_sorted_vec(tcx));
+    }
+    if opts.dump_reachable_set_modules {
+        dump_reachable_set_modules(tcx, reachable_set);
+    }
+    if let Some(ref path) = opts.dump_reachable_set_from {
+        let items = reachable_set.to_sorted_vec(tcx);
+        match items
+            .iter()
+            .find(|&&def_id| reachable_set.path_of(tcx, def_id).as_ref() == Some(path))
+        {
+            Some(&root) => {
+                let from_root = graph.transitive_references(tcx, root);
+                let items = items.into_iter().filter(|def_id| from_root.contains(def_id)).collect();
+                dump_reachable_items(tcx, graph, items);
+            }
+            None => {
+                tcx.dcx().emit_err(UnknownReachableRoot { path: path.clone() });
+            }
+        }
+    }
+    if let SwitchWithOptPath::Enabled(ref path) = opts.dump_reachable_set_json {
+        if let Err(err) =
+            dump_reachable_set_json(tcx, reachable_set, graph, path, tcx.crate_name(LOCAL_CRATE))
+        {
+            tcx.dcx().emit_fatal(CouldntDumpReachableSet { error: err.to_string() });
+        }
+    }
+    if let SwitchWithOptPath::Enabled(ref path) = opts.dump_reachable_set_dot {
+        if let Err(err) =
+            dump_reachable_set_dot(tcx, reachable_set, graph, path, tcx.crate_name(LOCAL_CRATE))
+        {
+            tcx.dcx().emit_fatal(CouldntDumpReachableSet { error: err.to_string() });
+        }
+    }
+}
+
+/// Emits a note for each of the given reachable items, along with the reason it was found to be
+/// reachable and the items it references. Used by `-Z dump-reachable-set` to debug why an item
+/// ended up in the reachable set, and by `-Z dump-reachable-set-from` to see what a single item
+/// pulls into it.
+fn dump_reachable_items(tcx: TyCtxt<'_>, graph: &ReachableGraph, mut items: Vec<LocalDefId>) {
+    // The crate root is always reachable, and its span covers the whole crate.
+    items.retain(|&def_id| def_id != CRATE_DEF_ID);
+    items.sort_by_cached_key(|&def_id| tcx.def_span(def_id));
+    let unreferenced_trait_impl_items = graph.unreferenced_trait_impl_items();
+    let uninstantiated_generics = graph.uninstantiated_generics(tcx);
+    for def_id in items {
+        let reason = match graph.reason(def_id).unwrap() {
+            ReachableReason::Exported => "exported".to_string(),
+            ReachableReason::LangItem => "lang item".to_string(),
+            ReachableReason::CustomLinkage => "custom linkage".to_string(),
+            ReachableReason::TraitImplItem => "item of a trait impl".to_string(),
+            ReachableReason::ReferencedFrom(from) => {
+                format!("referenced from `{}`", tcx.def_path_str(from))
+            }
+        };
+        let references = graph.references_from(def_id).map(|references| {
+            references
+                .items()
+                .map(|&reference| format!("`{}`", tcx.def_path_str(reference)))
+                .into_sorted_stable_ord()
+                .join(", ")
+        });
+        // Upstream items are printed with their full paths, as whether a trimmed path is
+        // unambiguous depends on everything else that is in scope of the upstream crates.
+        let external_references = graph.external_references_from(def_id).map(|references| {
+            references
+                .items()
+                .map(|&reference| {
+                    format!("`{}`", with_no_trimmed_paths!(tcx.def_path_str(reference)))
+                })
+                .into_sorted_stable_ord()
+                .join(", ")
+        });
+        tcx.dcx().emit_note(ReachableItem {
+            span: tcx.def_span(def_id),
+            item: tcx.def_path_str(def_id),
+            reason,
+            references: references.is_some().then_some(()),
+            referenced: references.unwrap_or_default(),
+            external_references: external_references.is_some().then_some(()),
+            externally_referenced: external_references.unwrap_or_default(),
+            unreferenced: unreferenced_trait_impl_items.contains(&def_id).then_some(()),
+            uninstantiated: uninstantiated_generics.contains(&def_id).then_some(()),
+        });
+    }
+}
+
+/// Emits a note for each module with the number of its items that are reachable and the paths of
+/// those that are not, for `-Z dump-reachable-set-modules`. The modules are sorted by path,
+/// starting with the crate root.
+fn dump_reachable_set_modules(tcx: TyCtxt<'_>, reachable_set: &ReachableSet) {
+    let mut unreachable: UnordMap<LocalModDefId, Vec<String>> = UnordMap::default();
+    for def_id in reachable_set.unreachable_items(tcx) {
+        let path = with_no_trimmed_paths!(tcx.def_path_str(def_id));
+        unreachable
+            .entry(tcx.parent_module_from_def_id(def_id))
+            .or_default()
+            .push(format!("`{path}`"));
+    }
+    let modules = reachable_set
+        .module_report(tcx)
+        .into_items()
+        .map(|(module, (reachable, total))| {
+            let unreachable = unreachable.get(&module).map(|paths| paths.join(", "));
+            (tcx.def_path_str(module), reachable, total, unreachable)
+        })
+        .into_sorted_stable_ord_by_key(|(path, ..)| path);
+    for (path, reachable, total, unreachable_items) in modules {
+        let module =
+            if path.is_empty() { "the crate root".to_string() } else { format!("`{path}`") };
+        tcx.dcx().emit_note(ReachableModule {
+            module,
+            reachable,
+            total,
+            unreachable: unreachable_items.is_some().then_some(()),
+            unreachable_items: unreachable_items.unwrap_or_default(),
+        });
+    }
+}
+
+/// Writes the reachable items, sorted by path, to `{crate_name}.reachable.json` for
+/// `-Z dump-reachable-set-json`, so that the reachable sets of two builds can be compared.
+fn dump_reachable_set_json(
+    tcx: TyCtxt<'_>,
+    reachable_set: &ReachableSet,
+    graph: &ReachableGraph,
+    output_directory: &Option<PathBuf>,
+    crate_name: Symbol,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let file = create_dump_file(output_directory, &format!("{crate_name}.reachable.json"))?;
+
+    #[derive(serde::Serialize)]
+    struct DumpedItem {
+        path: String,
+        reason: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        referenced_from: Option<String>,
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        inlinable_body: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        expr_count: Option<usize>,
+    }
+
+    let mut items: Vec<_> = reachable_set
+        .to_sorted_vec(tcx)
+        .into_iter()
+        // The crate root is always reachable, and its path is empty.
+        .filter(|&def_id| def_id != CRATE_DEF_ID)
+        .map(|def_id| {
+            let (reason, referenced_from) = match graph.reason(def_id).unwrap() {
+                ReachableReason::Exported => ("exported", None),
+                ReachableReason::LangItem => ("lang_item", None),
+                ReachableReason::CustomLinkage => ("custom_linkage", None),
+                ReachableReason::TraitImplItem => ("trait_impl_item", None),
+                ReachableReason::ReferencedFrom(from) => {
+                    ("referenced", reachable_set.path_of(tcx, from))
+                }
+            };
+            let path = reachable_set.path_of(tcx, def_id).unwrap();
+            let inlinable_body = graph.has_inlinable_body(def_id);
+            let expr_count = graph.expr_count(def_id);
+            DumpedItem { path, reason, referenced_from, inlinable_body, expr_count }
+        })
+        .collect();
+    items.sort_by(|a, b| a.path.cmp(&b.path));
+
+    serde_json::to_writer(file, &items)?;
+    Ok(())
+}
+
+/// Writes the reachable items and the references between them as a Graphviz graph to
+/// `{crate_name}.reachable.dot` for `-Z dump-reachable-set-dot`.
+fn dump_reachable_set_dot(
+    tcx: TyCtxt<'_>,
+    reachable_set: &ReachableSet,
+    graph: &ReachableGraph,
+    output_directory: &Option<PathBuf>,
+    crate_name: Symbol,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = create_dump_file(output_directory, &format!("{crate_name}.reachable.dot"))?;
+    file.write_all(graph.to_dot(tcx, reachable_set).as_bytes())?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Creates `file_name` in `output_directory`, or in the current directory if there is none.
+fn create_dump_file(
+    output_directory: &Option<PathBuf>,
+    file_name: &str,
+) -> std::io::Result<BufWriter<File>> {
+    let output_directory = if let Some(ref directory) = output_directory {
+        fs::create_dir_all(directory)?;
+        directory
+    } else {
+        Path::new(".")
+    };
+    Ok(BufWriter::new(File::create(output_directory.join(file_name))?))
 }
 
 pub fn provide(providers: &mut Providers) {
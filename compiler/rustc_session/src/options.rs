@@ -1630,6 +1630,23 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "output statistics about monomorphization collection"),
     dump_mono_stats_format: DumpMonoStatsFormat = (DumpMonoStatsFormat::Markdown, parse_dump_mono_stats, [UNTRACKED],
         "the format to use for -Z dump-mono-stats (`markdown` (default) or `json`)"),
+    dump_reachable_set: bool = (false, parse_bool, [UNTRACKED],
+        "emit a note for each externally reachable item and why it is reachable (default: no)"),
+    dump_reachable_set_json: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write the externally reachable items and why they are reachable to \
+        `{crate_name}.reachable.json` in the given directory (default: the current directory)"),
+    dump_reachable_set_dot: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write the externally reachable items and the references between them as a Graphviz \
+        graph to `{crate_name}.reachable.dot` in the given directory (default: the current \
+        directory)"),
+    dump_reachable_set_from: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "like `-Z dump-reachable-set`, but only for the item with the given path and the items \
+        it transitively references"),
+    dump_reachable_set_modules: bool = (false, parse_bool, [UNTRACKED],
+        "emit a note for each module with how many of its items are externally reachable \
+        (default: no)"),
     dwarf_version: Option<u32> = (None, parse_opt_number, [TRACKED],
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
     dylib_lto: bool = (false, parse_bool, [UNTRACKED],
@@ -1860,6 +1877,23 @@ pub(crate) fn parse_function_return(slot: &mut FunctionReturn, v: Option<&str>)
         "enable queries of the dependency graph for regression testing (default: no)"),
     randomize_layout: bool = (false, parse_bool, [TRACKED],
         "randomize the layout of types (default: no)"),
+    reachable_all_bodies: bool = (false, parse_bool, [UNTRACKED],
+        "scan the bodies of all reachable functions, not only of inlinable ones, so that every \
+        transitively referenced item is reachable; only affects the output of \
+        `-Z dump-reachable-set` and related options, not the items that are exported or the \
+        MIR that is encoded (default: no)"),
+    reachable_max_depth: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "stop scanning items for references this many references away from the roots of the \
+        reachable set; only affects the output of `-Z dump-reachable-set` and related options, \
+        not the items that are exported (default: no limit)"),
+    reachable_skip_trait_impls: bool = (false, parse_bool, [UNTRACKED],
+        "do not make the items of private trait impls reachable unless something refers to them, \
+        to measure how much of the reachable set they account for; only affects the output of \
+        `-Z dump-reachable-set` and related options, not the items that are exported \
+        (default: no)"),
+    reachable_set_warn_percent: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "warn if more than this percentage of the items of the crate are reachable from \
+        other crates (default: never warn)"),
     relax_elf_relocations: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "whether ELF relocations can be relaxed"),
     relro_level: Option<RelroLevel> = (None, parse_relro_level, [TRACKED],
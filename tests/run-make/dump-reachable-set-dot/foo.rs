@@ -0,0 +1,9 @@
+#[inline]
+pub fn bar() {
+    baz();
+}
+
+#[inline(never)]
+fn baz() {}
+
+pub fn qux() {}
@@ -0,0 +1,7 @@
+#[inline]
+pub fn bar() {
+    baz();
+}
+
+#[inline(never)]
+fn baz() {}
@@ -0,0 +1,11 @@
+#[inline]
+pub fn bar() {
+    baz();
+}
+
+#[inline(never)]
+fn baz() {}
+
+pub mod inner {
+    pub fn qux() {}
+}
@@ -0,0 +1,18 @@
+#[inline]
+pub fn root() {
+    target();
+    far();
+}
+
+#[inline]
+fn far() {
+    target();
+}
+
+#[inline]
+fn target() {
+    leaf();
+}
+
+#[inline(never)]
+fn leaf() {}
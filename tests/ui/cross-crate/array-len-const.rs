@@ -0,0 +1,11 @@
+// Check that private constants used in the length of array types in public type
+// aliases and signatures can be evaluated by other crates.
+//@ run-pass
+//@ aux-build:array_len_const_aux.rs
+extern crate array_len_const_aux as aux;
+
+fn main() {
+    let a: aux::A = [0; 4];
+    assert_eq!(a.len(), 4);
+    assert_eq!(aux::make().len(), 4);
+}
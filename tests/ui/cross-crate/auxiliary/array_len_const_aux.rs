@@ -0,0 +1,12 @@
+const SIZE: usize = size();
+
+const fn size() -> usize {
+    4
+}
+
+pub type A = [u8; SIZE];
+
+#[inline]
+pub fn make() -> [u32; SIZE] {
+    [1; SIZE]
+}
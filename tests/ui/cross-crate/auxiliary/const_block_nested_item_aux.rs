@@ -0,0 +1,13 @@
+pub const GET: fn() -> u32 = {
+    #[inline]
+    fn get() -> u32 {
+        helper() + 1
+    }
+
+    get
+};
+
+#[inline(never)]
+fn helper() -> u32 {
+    2
+}
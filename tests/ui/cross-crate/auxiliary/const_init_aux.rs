@@ -0,0 +1,19 @@
+pub const F: fn() -> u32 = helper;
+pub const G: fn() -> u32 = Wrapper(generic_helper::<u32>).get();
+
+#[inline]
+fn helper() -> u32 {
+    42
+}
+
+fn generic_helper<T: Default>() -> T {
+    T::default()
+}
+
+struct Wrapper(fn() -> u32);
+
+impl Wrapper {
+    const fn get(self) -> fn() -> u32 {
+        self.0
+    }
+}
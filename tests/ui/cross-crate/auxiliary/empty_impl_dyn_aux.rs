@@ -0,0 +1,28 @@
+trait Describe {
+    fn describe(&self) -> u32 {
+        3
+    }
+}
+
+trait Marker {}
+
+struct S;
+
+impl Describe for S {}
+
+impl Marker for S {}
+
+fn marker_value<T: Marker>(_: &T) -> u32 {
+    4
+}
+
+#[inline]
+pub fn via_dyn() -> u32 {
+    let d: &dyn Describe = &S;
+    d.describe()
+}
+
+#[inline]
+pub fn via_marker() -> u32 {
+    marker_value(&S)
+}
@@ -0,0 +1,14 @@
+mod private {
+    #[export_name = "export_name_root_aux_get"]
+    extern "C" fn get() -> u32 {
+        super::helper()
+    }
+
+    #[export_name = "EXPORT_NAME_ROOT_AUX_VALUE"]
+    static VALUE: u32 = 9;
+}
+
+#[inline]
+fn helper() -> u32 {
+    8
+}
@@ -0,0 +1,37 @@
+mod private {
+    pub struct Inner<T>(pub T);
+
+    impl<T: Copy> Inner<T> {
+        #[inline]
+        pub fn get(&self) -> T {
+            self.0
+        }
+    }
+
+    #[repr(C)]
+    pub struct Repr(u32);
+
+    impl Repr {
+        #[inline]
+        pub fn value(&self) -> u32 {
+            self.0
+        }
+    }
+
+    #[no_mangle]
+    extern "C" fn field_and_foreign_types_make_repr() -> Repr {
+        Repr(7)
+    }
+}
+
+pub struct Outer {
+    pub inner: private::Inner<u32>,
+}
+
+pub fn make() -> Outer {
+    Outer { inner: private::Inner(5) }
+}
+
+extern "C" {
+    pub fn field_and_foreign_types_make_repr() -> private::Repr;
+}
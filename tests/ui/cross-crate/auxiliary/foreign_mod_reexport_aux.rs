@@ -0,0 +1,23 @@
+mod ffi {
+    extern "C" {
+        pub fn foreign_mod_double(x: u32) -> u32;
+        pub fn foreign_mod_triple(x: u32) -> u32;
+    }
+}
+
+pub use ffi::foreign_mod_double as double;
+
+#[inline]
+pub fn triple(x: u32) -> u32 {
+    unsafe { ffi::foreign_mod_triple(x) }
+}
+
+#[no_mangle]
+extern "C" fn foreign_mod_double(x: u32) -> u32 {
+    x * 2
+}
+
+#[no_mangle]
+extern "C" fn foreign_mod_triple(x: u32) -> u32 {
+    x * 3
+}
@@ -0,0 +1,24 @@
+pub trait Bound {
+    fn value(&self) -> u32 {
+        helper() * 2
+    }
+}
+
+pub struct Holder<T>(pub T);
+
+impl<T: Bound> Holder<T> {
+    pub fn run(&self) -> u32 {
+        self.0.value()
+    }
+}
+
+pub fn run_where<T>(t: &T) -> u32
+where
+    T: Bound,
+{
+    t.value()
+}
+
+fn helper() -> u32 {
+    5
+}
@@ -0,0 +1,26 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+struct Resource;
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+pub struct Holder<T> {
+    _value: T,
+    _resource: Resource,
+}
+
+impl<T> Holder<T> {
+    pub fn new(value: T) -> Self {
+        Holder { _value: value, _resource: Resource }
+    }
+}
+
+pub fn drops() -> usize {
+    DROPS.load(Ordering::SeqCst)
+}
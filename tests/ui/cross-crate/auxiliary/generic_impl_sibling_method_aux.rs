@@ -0,0 +1,37 @@
+pub trait Weight {
+    fn weight(&self) -> u32;
+}
+
+impl Weight for u8 {
+    fn weight(&self) -> u32 {
+        u32::from(*self)
+    }
+}
+
+impl Weight for &str {
+    fn weight(&self) -> u32 {
+        self.len() as u32
+    }
+}
+
+pub struct Counter<T> {
+    value: T,
+}
+
+impl<T: Weight> Counter<T> {
+    pub fn new(value: T) -> Self {
+        Counter { value }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.base() + 1
+    }
+
+    fn base(&self) -> u32 {
+        scale(self.value.weight())
+    }
+}
+
+fn scale(weight: u32) -> u32 {
+    weight
+}
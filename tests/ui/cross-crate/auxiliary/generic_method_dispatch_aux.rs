@@ -0,0 +1,30 @@
+pub trait Public {
+    fn get(&self) -> u32 {
+        helper() * 2
+    }
+}
+
+impl Public for u8 {}
+
+pub fn call_param<T: Public>(t: &T) -> u32 {
+    t.get()
+}
+
+pub struct Wrapper<T>(pub T);
+
+trait Private {
+    fn get(&self) -> u32 {
+        helper() + 1
+    }
+}
+
+impl<T> Private for Wrapper<T> {}
+
+pub fn call_wrapper<T>(w: &Wrapper<T>) -> u32 {
+    w.get()
+}
+
+#[inline]
+fn helper() -> u32 {
+    3
+}
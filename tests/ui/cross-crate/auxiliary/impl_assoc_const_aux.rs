@@ -0,0 +1,14 @@
+pub struct Wrapper<T>(pub T);
+
+impl<T> Wrapper<T> {
+    pub const SIZE: usize = size_of_helper::<T>();
+    pub const GET: fn() -> u32 = helper;
+}
+
+const fn size_of_helper<T>() -> usize {
+    std::mem::size_of::<T>()
+}
+
+fn helper() -> u32 {
+    7
+}
@@ -0,0 +1,20 @@
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+impl<T> Pair<T> {
+    fn new(first: T, second: T) -> Self {
+        Pair { first, second }
+    }
+
+    fn into_first(self) -> T {
+        drop(self.second);
+        self.first
+    }
+}
+
+#[inline]
+pub fn first_of<T>(first: T, second: T) -> T {
+    Pair::new(first, second).into_first()
+}
@@ -0,0 +1,22 @@
+#[inline]
+pub fn make_adder(x: u32) -> impl Fn(u32) -> u32 {
+    move |y| helper(x) + not_inline(y)
+}
+
+pub fn make_generic<T: Copy>(x: T) -> impl Fn() -> (T, u32) {
+    move || (x, nested(|| helper(1)))
+}
+
+#[inline]
+fn helper(x: u32) -> u32 {
+    x * 10
+}
+
+fn not_inline(y: u32) -> u32 {
+    y + 1
+}
+
+#[inline]
+fn nested<F: Fn() -> u32>(f: F) -> u32 {
+    f()
+}
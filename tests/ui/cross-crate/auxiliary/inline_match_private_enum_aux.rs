@@ -0,0 +1,24 @@
+enum Sign {
+    Zero,
+    Positive(u32),
+    Negative { magnitude: u32 },
+}
+
+fn sign(x: i32) -> Sign {
+    if x == 0 {
+        Sign::Zero
+    } else if x > 0 {
+        Sign::Positive(x as u32)
+    } else {
+        Sign::Negative { magnitude: -x as u32 }
+    }
+}
+
+#[inline]
+pub fn classify(x: i32) -> u32 {
+    match sign(x) {
+        Sign::Zero => 0,
+        Sign::Positive(n) => n * 2,
+        Sign::Negative { magnitude } => magnitude + 1,
+    }
+}
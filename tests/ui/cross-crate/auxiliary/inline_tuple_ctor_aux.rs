@@ -0,0 +1,19 @@
+struct Wrapper(u32);
+
+enum Choice {
+    Left(u32),
+    Right(u32),
+}
+
+#[inline]
+pub fn wrap_all(values: &[u32]) -> u32 {
+    values.iter().copied().map(Wrapper).map(|w| w.0).sum()
+}
+
+#[inline]
+pub fn pick(left: bool) -> u32 {
+    let make: fn(u32) -> Choice = if left { Choice::Left } else { Choice::Right };
+    match make(if left { 1 } else { 2 }) {
+        Choice::Left(n) | Choice::Right(n) => n,
+    }
+}
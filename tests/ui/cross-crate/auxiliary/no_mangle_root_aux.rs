@@ -0,0 +1,20 @@
+mod private {
+    #[no_mangle]
+    pub extern "C" fn no_mangle_root_aux_get() -> u32 {
+        super::helper() + super::Counter(1).next()
+    }
+}
+
+#[inline]
+fn helper() -> u32 {
+    5
+}
+
+struct Counter(u32);
+
+impl Counter {
+    #[inline]
+    fn next(self) -> u32 {
+        self.0 + 1
+    }
+}
@@ -0,0 +1,18 @@
+mod private {
+    pub trait Describe {
+        fn describe(&self) -> u32 {
+            base() + 2
+        }
+    }
+
+    #[inline(never)]
+    fn base() -> u32 {
+        40
+    }
+}
+
+pub use private::Describe;
+
+pub fn describe<T: Describe>(value: &T) -> u32 {
+    <T as Describe>::describe(value)
+}
@@ -0,0 +1,19 @@
+pub type List = Option<Link>;
+pub type Link = Box<Node>;
+
+pub struct Node {
+    value: u32,
+    next: List,
+}
+
+#[inline]
+pub fn cons(value: u32, next: List) -> List {
+    Some(Box::new(Node { value, next }))
+}
+
+pub fn sum(list: &List) -> u32 {
+    match *list {
+        Some(ref node) => node.value + sum(&node.next),
+        None => 0,
+    }
+}
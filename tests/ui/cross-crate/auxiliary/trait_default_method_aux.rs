@@ -0,0 +1,18 @@
+pub trait Tr {
+    fn go(&self) -> u32 {
+        helper() + generic_helper(self)
+    }
+}
+
+pub struct S;
+
+impl Tr for S {}
+
+#[inline]
+fn helper() -> u32 {
+    7
+}
+
+fn generic_helper<T: ?Sized>(_: &T) -> u32 {
+    1
+}
@@ -0,0 +1,24 @@
+mod private {
+    pub trait Extra {
+        fn extra(&self) -> u32 {
+            super::helper() + 1
+        }
+    }
+
+    impl Extra for u8 {}
+}
+
+pub struct Wrapper<T>(pub T);
+
+impl<T> Wrapper<T>
+where
+    T: private::Extra,
+{
+    pub fn get(&self) -> u32 {
+        self.0.extra()
+    }
+}
+
+fn helper() -> u32 {
+    41
+}
@@ -0,0 +1,9 @@
+// Check that the items declared in a block in the initializer of a public constant are
+// reachable when the constant refers to them.
+//@ run-pass
+//@ aux-build:const_block_nested_item_aux.rs
+extern crate const_block_nested_item_aux as aux;
+
+fn main() {
+    assert_eq!((aux::GET)(), 3);
+}
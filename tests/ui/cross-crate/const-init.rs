@@ -0,0 +1,13 @@
+// Check that private functions referenced from the initializer of a public
+// `const` are reachable from other crates.
+//@ run-pass
+//@ aux-build:const_init_aux.rs
+extern crate const_init_aux as aux;
+
+static F: fn() -> u32 = aux::F;
+
+fn main() {
+    assert_eq!(aux::F(), 42);
+    assert_eq!(F(), 42);
+    assert_eq!(aux::G(), 0);
+}
@@ -0,0 +1,19 @@
+// Check that an unnameable trait is reachable when a public generic function returns one
+// of its associated types, along with the items of the trait.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+mod private {
+    pub trait Produce {
+        type Output;
+
+        fn make(&self) -> Self::Output;
+    }
+}
+
+pub fn produce_with<T: private::Produce>(value: &T) -> T::Output {
+    value.make()
+}
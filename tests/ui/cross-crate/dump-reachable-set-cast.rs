@@ -0,0 +1,19 @@
+// Check that the private type alias that an inlinable function casts to is reachable,
+// along with the private type it stands for.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+#![allow(dead_code)]
+
+struct Private {
+    value: u32,
+}
+
+type Handle = *const Private;
+
+#[inline]
+pub fn address(value: &u32) -> usize {
+    value as *const u32 as Handle as usize
+}
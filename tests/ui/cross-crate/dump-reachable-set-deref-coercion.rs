@@ -0,0 +1,31 @@
+// Check that the `deref` method of a private `Deref` impl is reachable when an inlinable
+// function relies on a deref coercion through it, not only as an item of a trait impl.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set -Z reachable-skip-trait-impls
+
+#![crate_type = "lib"]
+
+use std::ops::Deref;
+
+struct Wrapper {
+    value: u32,
+}
+
+impl Deref for Wrapper {
+    type Target = u32;
+
+    fn deref(&self) -> &u32 {
+        &self.value
+    }
+}
+
+#[inline]
+pub fn read() -> u32 {
+    let wrapper = Wrapper { value: 1 };
+    take(&wrapper)
+}
+
+fn take(value: &u32) -> u32 {
+    *value
+}
@@ -0,0 +1,16 @@
+// Check that the items named in the explicit discriminants of a reachable enum are reachable,
+// as other crates evaluate the discriminants like constants.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set-from=E
+
+#![crate_type = "lib"]
+
+#[repr(u32)]
+pub enum E {
+    A = base() + 1,
+}
+
+const fn base() -> u32 {
+    10
+}
@@ -0,0 +1,21 @@
+// Check that the local types named in the signature of a foreign function are reachable
+// when the foreign function is referenced from an inlinable function.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[repr(C)]
+struct Private {
+    value: u32,
+}
+
+extern "C" {
+    fn ext(private: Private) -> Private;
+}
+
+#[inline]
+pub fn call() -> u32 {
+    unsafe { ext(Private { value: 1 }).value }
+}
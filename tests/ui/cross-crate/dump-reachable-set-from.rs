@@ -0,0 +1,28 @@
+// Check that `-Z dump-reachable-set-from` only reports the given item and the items it
+// transitively references.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set-from=outer
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn outer() {
+    middle();
+}
+
+#[inline]
+fn middle() {
+    inner();
+}
+
+#[inline(never)]
+fn inner() {}
+
+#[inline]
+pub fn unrelated() {
+    other();
+}
+
+#[inline(never)]
+fn other() {}
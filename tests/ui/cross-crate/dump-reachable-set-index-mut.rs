@@ -0,0 +1,35 @@
+// Check that the methods of the private `Index` and `IndexMut` impls that an inlinable function
+// indexes a container through are reachable because the function refers to them, including
+// `IndexMut::index_mut` for the place of a compound assignment.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set -Z reachable-skip-trait-impls
+
+#![crate_type = "lib"]
+
+use std::ops::{Index, IndexMut};
+
+struct Slots {
+    values: [u32; 2],
+}
+
+impl Index<usize> for Slots {
+    type Output = u32;
+
+    fn index(&self, index: usize) -> &u32 {
+        &self.values[index]
+    }
+}
+
+impl IndexMut<usize> for Slots {
+    fn index_mut(&mut self, index: usize) -> &mut u32 {
+        &mut self.values[index]
+    }
+}
+
+#[inline]
+pub fn bump(value: u32) -> u32 {
+    let mut slots = Slots { values: [value, 0] };
+    slots[0] += 1;
+    slots[0]
+}
@@ -0,0 +1,29 @@
+// Check that a chain of inlinable functions, each calling the next one, is followed link by
+// link, each function being reachable because the one before it refers to it. The reachability
+// pass follows such chains with a worklist rather than by recursing, so their length is not
+// limited by the size of the stack.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn first() -> u32 {
+    second() + 1
+}
+
+#[inline]
+fn second() -> u32 {
+    third() + 1
+}
+
+#[inline]
+fn third() -> u32 {
+    last() + 1
+}
+
+#[inline(never)]
+fn last() -> u32 {
+    1
+}
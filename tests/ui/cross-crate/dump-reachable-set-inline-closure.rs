@@ -0,0 +1,18 @@
+// Check that closures are only scanned as part of the inlinable function that defines them,
+// even when their call names them again, so that what they reference is recorded once.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn run() -> u32 {
+    let f = || helper();
+    f()
+}
+
+#[inline(never)]
+fn helper() -> u32 {
+    1
+}
@@ -0,0 +1,17 @@
+// Check that local lang items are reachable whether or not anything refers to them, so
+// that the operations of other crates that use them implicitly, such as allocations, can.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![feature(no_core, lang_items)]
+#![no_core]
+#![crate_type = "lib"]
+
+#[lang = "sized"]
+trait Sized {}
+
+#[lang = "exchange_malloc"]
+unsafe fn allocate(_size: usize, _align: usize) -> *mut u8 {
+    loop {}
+}
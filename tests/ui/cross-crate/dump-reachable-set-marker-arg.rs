@@ -0,0 +1,15 @@
+// Check that a private type that is only used as a generic argument in an inlinable
+// function is reachable.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[allow(dead_code)]
+struct Marker;
+
+#[inline]
+pub fn marker_size() -> usize {
+    std::mem::size_of::<Marker>()
+}
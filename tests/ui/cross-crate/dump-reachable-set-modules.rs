@@ -0,0 +1,24 @@
+// Check that `-Z dump-reachable-set-modules` reports how many of the items of each module
+// are reachable, and lists the private items that nothing refers to.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set-modules
+
+#![crate_type = "lib"]
+#![allow(dead_code)]
+
+pub mod loose {
+    pub fn first() {}
+
+    pub fn second() {}
+}
+
+pub mod tight {
+    pub fn api() {}
+
+    fn helper() {}
+
+    fn other() {}
+}
+
+fn unused() {}
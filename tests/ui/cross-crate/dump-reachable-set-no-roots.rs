@@ -0,0 +1,17 @@
+// Check that the reachable set of a library that exports nothing, and that has no trait
+// impls and no items with custom linkage, only contains the crate root, so that
+// `-Z dump-reachable-set` reports nothing.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[allow(dead_code)]
+#[inline]
+fn private() {
+    helper();
+}
+
+#[allow(dead_code)]
+fn helper() {}
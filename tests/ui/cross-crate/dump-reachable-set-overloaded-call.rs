@@ -0,0 +1,26 @@
+// Check that the method of the private `Fn*` trait impl that an inlinable function calls a value
+// through is reachable because the function refers to it, not only as an item of a trait impl.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set -Z reachable-skip-trait-impls
+
+#![crate_type = "lib"]
+#![feature(fn_traits, unboxed_closures)]
+
+struct Scale {
+    factor: u32,
+}
+
+impl FnOnce<(u32,)> for Scale {
+    type Output = u32;
+
+    extern "rust-call" fn call_once(self, args: (u32,)) -> u32 {
+        args.0 * self.factor
+    }
+}
+
+#[inline]
+pub fn double(x: u32) -> u32 {
+    let scale = Scale { factor: 2 };
+    scale(x)
+}
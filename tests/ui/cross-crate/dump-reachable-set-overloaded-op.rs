@@ -0,0 +1,35 @@
+// Check that the methods of the private operator impls that an inlinable function uses are
+// reachable because the function refers to them, not only as items of trait impls.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set -Z reachable-skip-trait-impls
+
+#![crate_type = "lib"]
+
+use std::ops::{BitAnd, Not};
+
+struct Flag {
+    set: bool,
+}
+
+impl Not for Flag {
+    type Output = bool;
+
+    fn not(self) -> bool {
+        !self.set
+    }
+}
+
+impl BitAnd for Flag {
+    type Output = bool;
+
+    fn bitand(self, other: Flag) -> bool {
+        self.set && other.set
+    }
+}
+
+#[inline]
+pub fn check() -> bool {
+    let unset = !Flag { set: false };
+    Flag { set: true } & Flag { set: unset }
+}
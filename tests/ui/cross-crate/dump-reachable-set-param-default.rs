@@ -0,0 +1,17 @@
+// Check that an unnameable type that is only used as the default of a type parameter of a
+// public type is reachable.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+use std::marker::PhantomData;
+
+mod private {
+    pub struct DefaultMarker {}
+}
+
+pub struct Wrapper<T = private::DefaultMarker> {
+    _marker: PhantomData<T>,
+}
@@ -0,0 +1,29 @@
+// Check that the private variants and constants that an inlinable function only names in
+// patterns are reachable because the function refers to them.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+const ZERO: u32 = 0;
+
+enum Kind {
+    Empty,
+    Other,
+}
+
+fn kind_of(value: u32) -> Kind {
+    if value == 1 { Kind::Empty } else { Kind::Other }
+}
+
+#[inline]
+pub fn classify(value: u32) -> u32 {
+    match value {
+        ZERO => 0,
+        _ => match kind_of(value) {
+            Kind::Empty => 1,
+            Kind::Other => 2,
+        },
+    }
+}
@@ -0,0 +1,15 @@
+// Check that an item that is both exported and referenced from an exported inline
+// function is reported as exported.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn caller() {
+    callee();
+}
+
+#[inline]
+pub fn callee() {}
@@ -0,0 +1,12 @@
+// Check that a recursive inline function is reported to reference itself once, however
+// many times it calls itself.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn countdown(n: u32) -> u32 {
+    if n == 0 { 0 } else { countdown(n - 1) + countdown(0) }
+}
@@ -0,0 +1,14 @@
+// Check that `-Z dump-reachable-set` reports the items referenced from an inlinable function.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn inlined() {
+    callee();
+}
+
+#[inline(never)]
+fn callee() {}
@@ -0,0 +1,30 @@
+// Check that an item referenced from several scanned items is reported as referenced from one
+// of those closest to the roots, rather than from itself or from another item of its cycle.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn root() {
+    a(0);
+    shared();
+}
+
+#[inline]
+fn a(n: u32) {
+    if n > 0 {
+        a(n - 1);
+        b(n - 1);
+    }
+    shared();
+}
+
+#[inline]
+fn b(n: u32) {
+    a(n)
+}
+
+#[inline(never)]
+fn shared() {}
@@ -0,0 +1,13 @@
+// Check that the bodies of closures that the initializers of exported statics point
+// to are scanned, so that the functions they call are reachable.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+pub static CALLBACK: fn() -> u32 = || helper() + 1;
+
+fn helper() -> u32 {
+    41
+}
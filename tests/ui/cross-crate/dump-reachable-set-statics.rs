@@ -0,0 +1,21 @@
+// Check that the functions that the initializers of exported statics point to are
+// reachable, including for `static mut`, while functions that are only called during
+// the evaluation of an initializer are not.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+pub static mut HOOK: fn() -> u32 = default_hook;
+
+fn default_hook() -> u32 {
+    0
+}
+
+pub static LIMIT: u32 = limit();
+
+#[inline]
+const fn limit() -> u32 {
+    16
+}
@@ -0,0 +1,18 @@
+// Check that a private struct built with struct expressions, including a functional record
+// update, in a generic function is reachable.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+struct Config {
+    size: u32,
+    depth: u32,
+}
+
+pub fn with_size<T>(_marker: T, size: u32) -> u32 {
+    let base = Config { size: 1, depth: 2 };
+    let config = Config { size, ..base };
+    config.size + config.depth
+}
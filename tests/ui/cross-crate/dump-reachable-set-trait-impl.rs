@@ -0,0 +1,19 @@
+// Check that an impl of a private generic trait makes the default methods of the
+// trait reachable.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+trait Convert<T> {
+    fn convert(&self) -> Option<T> {
+        None
+    }
+}
+
+impl Convert<u32> for u8 {}
+
+pub fn to_u32(x: u8) -> Option<u32> {
+    x.convert()
+}
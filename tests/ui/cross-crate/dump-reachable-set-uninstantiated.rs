@@ -0,0 +1,20 @@
+// Check that exported generic functions that no inlinable body of a reachable item instantiates
+// are pointed out, unlike those that an inlinable function calls and non-generic functions.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+pub fn never_called<T: Default>() -> T {
+    T::default()
+}
+
+pub fn called<T: Clone>(value: &T) -> T {
+    value.clone()
+}
+
+#[inline]
+pub fn caller(value: &u32) -> u32 {
+    called(value)
+}
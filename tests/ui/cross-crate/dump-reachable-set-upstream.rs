@@ -0,0 +1,15 @@
+// Check that `-Z dump-reachable-set` reports the items of other crates that reachable
+// items reference.
+
+//@ build-pass
+//@ aux-build:reachable_upstream_aux.rs
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+extern crate reachable_upstream_aux;
+
+#[inline]
+pub fn call() -> u32 {
+    reachable_upstream_aux::helper()
+}
@@ -0,0 +1,13 @@
+// Check that a private `#[used]` static is a root of the reachable set, and that
+// the items its initializer refers to are reachable too.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#![crate_type = "lib"]
+
+#[used]
+static ROOT: fn() = helper;
+
+#[inline(never)]
+fn helper() {}
@@ -0,0 +1,9 @@
+// Check that `-Z dump-reachable-set` reports reachable items and why they are reachable.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set
+
+#[no_mangle]
+extern "C" fn custom_linkage() {}
+
+fn main() {}
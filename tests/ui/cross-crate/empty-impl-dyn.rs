@@ -0,0 +1,11 @@
+// Check that an empty impl of a private trait whose methods all have defaults can be
+// used as a trait object from the inline functions of other crates, and that an empty
+// impl of a private marker trait can be used as a bound.
+//@ run-pass
+//@ aux-build:empty_impl_dyn_aux.rs
+extern crate empty_impl_dyn_aux as aux;
+
+fn main() {
+    assert_eq!(aux::via_dyn(), 3);
+    assert_eq!(aux::via_marker(), 4);
+}
@@ -0,0 +1,15 @@
+// Check that items with `#[export_name]` are reachable from other crates
+// even though they are private and defined in a private module.
+//@ run-pass
+//@ aux-build:export_name_root_aux.rs
+extern crate export_name_root_aux;
+
+extern "C" {
+    fn export_name_root_aux_get() -> u32;
+    static EXPORT_NAME_ROOT_AUX_VALUE: u32;
+}
+
+fn main() {
+    assert_eq!(unsafe { export_name_root_aux_get() }, 8);
+    assert_eq!(unsafe { EXPORT_NAME_ROOT_AUX_VALUE }, 9);
+}
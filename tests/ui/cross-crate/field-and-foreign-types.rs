@@ -0,0 +1,10 @@
+// Check that types only reachable through the fields of public structs and the
+// signatures of public foreign functions can be used from other crates.
+//@ run-pass
+//@ aux-build:field_and_foreign_types_aux.rs
+extern crate field_and_foreign_types_aux as aux;
+
+fn main() {
+    assert_eq!(aux::make().inner.get(), 5);
+    assert_eq!(unsafe { aux::field_and_foreign_types_make_repr() }.value(), 7);
+}
@@ -0,0 +1,10 @@
+// Check that items of a private foreign module are usable from other crates,
+// both when re-exported under another name and when used by an inline function.
+//@ run-pass
+//@ aux-build:foreign_mod_reexport_aux.rs
+extern crate foreign_mod_reexport_aux as aux;
+
+fn main() {
+    assert_eq!(unsafe { aux::double(21) }, 42);
+    assert_eq!(aux::triple(14), 42);
+}
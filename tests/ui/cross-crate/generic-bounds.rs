@@ -0,0 +1,14 @@
+// Check that default methods of local traits used as bounds of generic impls and
+// where clauses are reachable from other crates, along with what they call.
+//@ run-pass
+//@ aux-build:generic_bounds_aux.rs
+extern crate generic_bounds_aux as aux;
+
+struct Local;
+
+impl aux::Bound for Local {}
+
+fn main() {
+    assert_eq!(aux::Holder(Local).run(), 10);
+    assert_eq!(aux::run_where(&Local), 10);
+}
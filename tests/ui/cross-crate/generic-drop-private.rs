@@ -0,0 +1,13 @@
+// Check that the `Drop` impl of a private type is reachable from other crates when
+// values of the type are dropped by generic code instantiated there.
+//@ run-pass
+//@ aux-build:generic_drop_private_aux.rs
+extern crate generic_drop_private_aux as aux;
+
+fn main() {
+    {
+        let _holder = aux::Holder::new(5u8);
+        assert_eq!(aux::drops(), 0);
+    }
+    assert_eq!(aux::drops(), 1);
+}
@@ -0,0 +1,11 @@
+// Check that the private methods of a generic impl that its public methods call are
+// reachable from other crates, along with the functions they call in turn.
+//@ run-pass
+//@ aux-build:generic_impl_sibling_method_aux.rs
+extern crate generic_impl_sibling_method_aux as aux;
+
+fn main() {
+    let counter = aux::Counter::new(5u8);
+    assert_eq!(counter.total(), 6);
+    assert_eq!(aux::Counter::new("abc").total(), 4);
+}
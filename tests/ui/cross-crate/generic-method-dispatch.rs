@@ -0,0 +1,15 @@
+// Check that default trait methods called through a type parameter are
+// reachable from other crates, along with what they call.
+//@ run-pass
+//@ aux-build:generic_method_dispatch_aux.rs
+extern crate generic_method_dispatch_aux as aux;
+
+struct Local;
+
+impl aux::Public for Local {}
+
+fn main() {
+    assert_eq!(aux::call_param(&0u8), 6);
+    assert_eq!(aux::call_param(&Local), 6);
+    assert_eq!(aux::call_wrapper(&aux::Wrapper("x")), 4);
+}
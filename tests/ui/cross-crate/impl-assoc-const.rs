@@ -0,0 +1,10 @@
+// Check that private functions referenced from the initializer of an associated
+// constant in a generic impl are reachable from other crates.
+//@ run-pass
+//@ aux-build:impl_assoc_const_aux.rs
+extern crate impl_assoc_const_aux as aux;
+
+fn main() {
+    assert_eq!(aux::Wrapper::<u64>::SIZE, 8);
+    assert_eq!((aux::Wrapper::<u8>::GET)(), 7);
+}
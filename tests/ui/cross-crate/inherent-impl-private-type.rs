@@ -0,0 +1,10 @@
+// Check that the methods of a generic inherent impl on a private type can be
+// used from the inline functions of other crates.
+//@ run-pass
+//@ aux-build:inherent_impl_private_type_aux.rs
+extern crate inherent_impl_private_type_aux as aux;
+
+fn main() {
+    assert_eq!(aux::first_of(4u8, 9u8), 4);
+    assert_eq!(aux::first_of("a", "b"), "a");
+}
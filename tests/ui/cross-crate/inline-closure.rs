@@ -0,0 +1,10 @@
+// Check that functions called from closures defined in inlinable functions
+// are reachable from other crates.
+//@ run-pass
+//@ aux-build:inline_closure_aux.rs
+extern crate inline_closure_aux as aux;
+
+fn main() {
+    assert_eq!(aux::make_adder(2)(3), 24);
+    assert_eq!(aux::make_generic('a')(), ('a', 10));
+}
@@ -0,0 +1,10 @@
+// Check that inline functions matching on a private enum can be used from other crates.
+//@ run-pass
+//@ aux-build:inline_match_private_enum_aux.rs
+extern crate inline_match_private_enum_aux as aux;
+
+fn main() {
+    assert_eq!(aux::classify(0), 0);
+    assert_eq!(aux::classify(5), 10);
+    assert_eq!(aux::classify(-5), 6);
+}
@@ -0,0 +1,11 @@
+// Check that inline functions can use the constructors of private tuple structs and
+// tuple variants, including as function values, from other crates.
+//@ run-pass
+//@ aux-build:inline_tuple_ctor_aux.rs
+extern crate inline_tuple_ctor_aux as aux;
+
+fn main() {
+    assert_eq!(aux::wrap_all(&[1, 2, 3]), 6);
+    assert_eq!(aux::pick(true), 1);
+    assert_eq!(aux::pick(false), 2);
+}
@@ -0,0 +1,13 @@
+// Check that a `#[no_mangle]` function is reachable from other crates even
+// though it is defined in a private module and not exported otherwise.
+//@ run-pass
+//@ aux-build:no_mangle_root_aux.rs
+extern crate no_mangle_root_aux;
+
+extern "C" {
+    fn no_mangle_root_aux_get() -> u32;
+}
+
+fn main() {
+    assert_eq!(unsafe { no_mangle_root_aux_get() }, 7);
+}
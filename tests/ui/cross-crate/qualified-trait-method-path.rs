@@ -0,0 +1,13 @@
+// Check that a provided trait method called through a qualified path from a generic
+// function can be instantiated in other crates, along with what its body refers to.
+//@ run-pass
+//@ aux-build:qualified_trait_method_path_aux.rs
+extern crate qualified_trait_method_path_aux as aux;
+
+struct Local;
+
+impl aux::Describe for Local {}
+
+fn main() {
+    assert_eq!(aux::describe(&Local), 42);
+}
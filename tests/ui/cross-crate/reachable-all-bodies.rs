@@ -0,0 +1,19 @@
+// Check that `-Z reachable-all-bodies` makes the items referenced from the bodies of
+// non-inlinable functions reachable, transitively.
+
+//@ build-pass
+//@ revisions: default all_bodies
+//@ compile-flags: -Z dump-reachable-set
+//@[all_bodies] compile-flags: -Z reachable-all-bodies
+
+#![crate_type = "lib"]
+
+pub fn outer() {
+    middle();
+}
+
+fn middle() {
+    inner();
+}
+
+fn inner() {}
@@ -0,0 +1,22 @@
+// Check that in an executable, the foreign items that a reachable inlinable function
+// refers to are reachable, like in libraries, also when `-Z reachable-all-bodies` scans the
+// bodies of all reachable functions.
+
+//@ build-pass
+//@ revisions: default all_bodies
+//@ compile-flags: -Z dump-reachable-set
+//@[all_bodies] compile-flags: -Z reachable-all-bodies
+
+extern "C" {
+    fn abs(value: i32) -> i32;
+}
+
+#[no_mangle]
+#[inline]
+extern "C" fn root() -> i32 {
+    unsafe { abs(-1) }
+}
+
+fn main() {
+    root();
+}
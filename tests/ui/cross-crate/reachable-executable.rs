@@ -0,0 +1,18 @@
+// Check that in an executable, items that are scanned for references only become
+// reachable if they have custom linkage or a non-Rust ABI, in both scanning modes.
+
+//@ build-pass
+//@ revisions: default all_bodies
+//@ compile-flags: -Z dump-reachable-set
+//@[all_bodies] compile-flags: -Z reachable-all-bodies
+
+#[no_mangle]
+extern "C" fn root() {
+    helper();
+}
+
+fn helper() {}
+
+fn main() {
+    root();
+}
@@ -0,0 +1,36 @@
+// Check that with `-Z reachable-max-depth`, an item first reached at the depth limit is
+// still scanned when it is reached again closer to the roots.
+
+//@ build-pass
+//@ compile-flags: -Z dump-reachable-set -Z reachable-max-depth=3
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn root() {
+    near();
+    far();
+}
+
+#[inline]
+fn near() {
+    target();
+}
+
+#[inline]
+fn far() {
+    farther();
+}
+
+#[inline]
+fn farther() {
+    target();
+}
+
+#[inline]
+fn target() {
+    leaf();
+}
+
+#[inline(never)]
+fn leaf() {}
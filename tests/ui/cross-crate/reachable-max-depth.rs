@@ -0,0 +1,28 @@
+// Check that `-Z reachable-max-depth` stops looking for references at the given
+// distance from the roots of the reachable set.
+
+//@ build-pass
+//@ revisions: depth1 depth2
+//@ compile-flags: -Z dump-reachable-set
+//@[depth1] compile-flags: -Z reachable-max-depth=1
+//@[depth2] compile-flags: -Z reachable-max-depth=2
+
+#![crate_type = "lib"]
+
+#[inline]
+pub fn first() {
+    second();
+}
+
+#[inline]
+fn second() {
+    third();
+}
+
+#[inline]
+fn third() {
+    fourth();
+}
+
+#[inline(never)]
+fn fourth() {}
@@ -0,0 +1,26 @@
+// Check that `-Z reachable-set-warn-percent` warns when more of the items of a crate are
+// reachable than allowed, and stays quiet otherwise.
+
+//@ build-pass
+//@ revisions: loose tight
+//@ compile-flags: -Z reachable-set-warn-percent=50
+
+#![crate_type = "lib"]
+
+#[cfg(loose)]
+pub fn first() {}
+
+#[cfg(loose)]
+pub fn second() {}
+
+#[cfg(tight)]
+pub fn api() {
+    first();
+    second();
+}
+
+#[cfg(tight)]
+fn first() {}
+
+#[cfg(tight)]
+fn second() {}
@@ -0,0 +1,22 @@
+// Check that `-Z reachable-skip-trait-impls` leaves the items of private trait impls that
+// nothing refers to out of the dumped reachable set.
+
+//@ build-pass
+//@ revisions: default skip
+//@ compile-flags: -Z dump-reachable-set
+//@[skip] compile-flags: -Z reachable-skip-trait-impls
+
+#![crate_type = "lib"]
+#![allow(dead_code)]
+
+struct Meters(u32);
+
+impl Clone for Meters {
+    fn clone(&self) -> Self {
+        Meters(self.0)
+    }
+}
+
+pub fn unit() -> u32 {
+    1
+}
@@ -0,0 +1,10 @@
+// Check that public type aliases referring to each other through a struct can be
+// used from other crates.
+//@ run-pass
+//@ aux-build:recursive_type_alias_aux.rs
+extern crate recursive_type_alias_aux as aux;
+
+fn main() {
+    let list: aux::List = aux::cons(1, aux::cons(2, None));
+    assert_eq!(aux::sum(&list), 3);
+}
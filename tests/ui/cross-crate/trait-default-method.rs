@@ -0,0 +1,16 @@
+// Check that private functions called from a default trait method are
+// reachable from other crates.
+//@ run-pass
+//@ aux-build:trait_default_method_aux.rs
+extern crate trait_default_method_aux as aux;
+
+use aux::Tr;
+
+struct Local;
+
+impl Tr for Local {}
+
+fn main() {
+    assert_eq!(aux::S.go(), 8);
+    assert_eq!(Local.go(), 8);
+}
@@ -0,0 +1,9 @@
+// Check that default methods of an unnameable trait used in the where clause of a
+// generic impl are reachable from other crates, along with what they call.
+//@ run-pass
+//@ aux-build:where_clause_private_trait_aux.rs
+extern crate where_clause_private_trait_aux as aux;
+
+fn main() {
+    assert_eq!(aux::Wrapper(0u8).get(), 42);
+}